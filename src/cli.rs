@@ -1,12 +1,20 @@
 //! # cli parsing
-use crate::scheme::Scheme;
-use clap::{Parser, ValueEnum};
-use css_named_colors::NamedColor;
+use crate::contrast::ContrastStandard;
+pub use crate::parse::ColorParseError;
+use crate::parse::parse_hsl;
+use crate::scheme::{
+    ColorScheme, Direction, Format, Order, OutputFormat, Override, Scheme, format_color,
+    precision_loss,
+};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+
+mod config;
 
 /// cli-facing equivalent of [`crate::scheme::Scheme`]
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum CliScheme {
-    /// lighter and darker variants of the same hue.    
+    /// lighter and darker variants of the same hue, at 1.5 and 0.5 times the
+    /// primary's lightness unless `--column-step` is given.    
     /// variable names: `--lighter`, `--darker`
     Column,
     /// the complementary color (180 degrees on the color wheel).    
@@ -22,49 +30,688 @@ pub enum CliScheme {
     /// clockwise, 180 degrees clockwise, 90 degrees counterclockwise).    
     /// variable names: `--upper-right`, `--lower-right`, `--lower-left`
     Tetrad,
+    /// a rectangle with the primary color as the upper-left corner (60, 180, and
+    /// 240 degrees clockwise).    
+    /// variable names: `--rect-upper-right`, `--rect-lower-right`, `--rect-lower-left`
+    Rectangle,
+    /// the two neighbors of the primary (30 degrees clockwise and counterclockwise).    
+    /// variable names: `--analogous-clockwise`, `--analogous-counterclockwise`
+    Analogous,
+    /// the two neighbors of the complementary color (150 and 210 degrees clockwise).    
+    /// variable names: `--split-complementary-clockwise`, `--split-complementary-counterclockwise`
+    SplitComplementary,
+    /// the complementary color and its two neighbors (180, 150, and 210 degrees clockwise).    
+    /// variable names: `--complementary`, `--compound-clockwise`, `--compound-counterclockwise`
+    Compound,
     /// a dark and saturated variant suitable for use as a font color.    
     /// variable names: `--text-primary`
     Text,
     /// a light and desaturated variant for use as a background color.    
     /// variable names: `--background-primary`
     Background,
+    /// light, mid, and dark near-grays tinted with the primary hue.    
+    /// variable names: `--neutral-light`, `--neutral`, `--neutral-dark`
+    Neutrals,
+    /// a soft variant at 40% saturation and 85% lightness.    
+    /// variable names: `--pastel-primary`
+    Pastel,
+    /// two lighter tints and two darker shades, 15 and 30 points of lightness
+    /// either side of the primary.    
+    /// variable names: `--tint-1`, `--tint-2`, `--shade-1`, `--shade-2`
+    Ramp,
+    /// the primary fully desaturated, keeping its lightness.    
+    /// variable names: `--grayscale`
+    Grayscale,
+    /// the primary at 90%, 50%, and 10% opacity.    
+    /// variable names: `--primary-90`, `--primary-50`, `--primary-10`
+    Alpha,
+    /// every scheme above; variables more than one generates are only emitted once
+    All,
+}
+
+impl CliScheme {
+    /// the schemes this stands for: itself, or every other scheme for [`CliScheme::All`]
+    fn expand(&self) -> Vec<&CliScheme> {
+        match self {
+            CliScheme::All => CliScheme::value_variants()
+                .iter()
+                .filter(|cli_scheme| **cli_scheme != CliScheme::All)
+                .collect(),
+            cli_scheme => vec![cli_scheme],
+        }
+    }
+}
+
+/// cli-facing equivalent of [`crate::scheme::Format`]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CliFormat {
+    /// `#rrggbb`, or `#rrggbbaa` for translucent colors
+    Hex,
+    /// `rgb(r, g, b)`, or `rgba(r, g, b, a)` for translucent colors
+    Rgb,
+    /// `hsl(h, s%, l%)`, or `hsla(h, s%, l%, a)` for translucent colors
+    Hsl,
+    /// `oklch(l% c h)`, or `oklch(l% c h / a)` for translucent colors
+    Oklch,
+}
+
+/// cli-facing equivalent of [`crate::scheme::OutputFormat`]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CliOutputFormat {
+    /// CSS custom properties
+    Css,
+    /// CSS custom properties pairing each color with a dark-mode variant in `light-dark()`
+    LightDark,
+    /// a Tailwind v4 `@theme` block of `--color-` variables; the selector is ignored
+    TailwindTheme,
+    /// an object for Tailwind's `theme.extend.colors` config; the selector is ignored
+    TailwindConfig,
+    /// a compact JSON object mapping variable names to values; the selector is ignored
+    Json,
+    /// Sass `$` variables; the selector is ignored
+    Scss,
+    /// an HTML page with a swatch per color, for previewing in a browser
+    Html,
+}
+
+/// cli-facing equivalent of [`crate::contrast::ContrastStandard`]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CliContrastStandard {
+    /// WCAG 2 AA, a contrast ratio of at least 4.5
+    Aa,
+    /// WCAG 2 AAA, a contrast ratio of at least 7
+    Aaa,
+    /// APCA, a lightness contrast of at least 75
+    Apca,
+}
+
+/// cli-facing equivalent of [`crate::scheme::Direction`]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CliDirection {
+    /// only colors clockwise of the primary
+    Cw,
+    /// only colors counterclockwise of the primary
+    Ccw,
+    /// colors on both sides of the primary
+    Both,
+}
+
+/// cli-facing equivalent of [`crate::scheme::Order`]
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CliOrder {
+    /// the primary, then each scheme's colors in the order requested
+    Insertion,
+    /// darkest to lightest by relative luminance, including the primary
+    Luminance,
+    /// the primary, then the other colors alphabetically by name
+    Alphabetical,
+    /// same-hue colors, then hue rotations, then text and background colors
+    Canonical,
 }
 
 /// cli arguments
 #[derive(Debug, Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     #[arg(
         short,
         long = "primary",
-        help = "primary scheme color (hex value or CSS color name)",
-        value_name = "PRIMARY COLOR"
+        help = "primary scheme color (hex value, CSS color name, or `rgb()`/`hsl()`/`hsv()` notation), or `-` to read one per line from stdin",
+        value_name = "PRIMARY COLOR",
+        required_unless_present_any = ["primary_hsl", "from", "config"],
+        conflicts_with_all = ["primary_hsl", "from"]
+    )]
+    primary_str: Option<String>,
+    #[arg(
+        long = "primary-hsl",
+        help = "primary scheme color as comma-separated hue, saturation, lightness, and optional alpha",
+        value_name = "H,S,L[,A]",
+        value_parser = parse_hsl
     )]
-    primary_str: String,
+    primary_hsl: Option<colorsys::Hsl>,
     #[arg(
         short = 's',
         long = "scheme",
         help = "color schemes to generate",
         value_name = "SCHEME",
-        required = true
+        required_unless_present_any = ["rotations", "rotate", "monochromatic", "polyad", "theme", "from", "config"]
     )]
     cli_schemes: Vec<CliScheme>,
+    #[arg(
+        long = "rotations",
+        help = "comma-separated angles in degrees to rotate the primary color by",
+        value_name = "DEGREES",
+        value_delimiter = ',',
+        value_parser = parse_degrees,
+        allow_negative_numbers = true
+    )]
+    rotations: Vec<f64>,
+    #[arg(
+        long = "rotate",
+        help = "an angle in degrees to rotate the primary color by, emitted as `--rotated-<DEGREES>`; can be repeated",
+        value_name = "DEGREES",
+        value_parser = parse_degrees,
+        allow_negative_numbers = true
+    )]
+    rotate: Vec<f64>,
+    #[arg(
+        long = "column-step",
+        help = "points of lightness to add and subtract for the column scheme, instead of scaling the primary's lightness",
+        value_name = "PERCENT",
+        value_parser = parse_column_step
+    )]
+    column_step: Option<f64>,
+    #[arg(
+        long = "perceptual",
+        help = "space the column and monochromatic lightnesses evenly in Oklab rather than HSL, so they look evenly spaced",
+        conflicts_with = "column_step"
+    )]
+    perceptual: bool,
+    #[arg(
+        long = "monochromatic",
+        help = "number of evenly spaced lightnesses of the primary to generate, from 10% to 90%",
+        value_name = "STEPS"
+    )]
+    monochromatic: Option<u8>,
+    #[arg(
+        long = "polyad",
+        help = "number of evenly spaced hues around the color wheel, including the primary",
+        value_name = "N"
+    )]
+    polyad: Option<u8>,
+    #[arg(
+        long = "from",
+        help = "first color of a ramp between two colors, instead of a scheme (hex value or CSS color name)",
+        value_name = "COLOR",
+        value_parser = parse_color,
+        requires = "to",
+        conflicts_with_all = [
+            "primary_hsl",
+            "cli_schemes",
+            "rotations",
+            "rotate",
+            "monochromatic",
+            "polyad",
+            "theme"
+        ]
+    )]
+    from: Option<colorsys::Hsl>,
+    #[arg(
+        long = "to",
+        help = "last color of a ramp between two colors (hex value or CSS color name)",
+        value_name = "COLOR",
+        value_parser = parse_color,
+        requires = "from"
+    )]
+    to: Option<colorsys::Hsl>,
+    #[arg(
+        long = "steps",
+        help = "number of colors in a ramp, including both ends (default: 5)",
+        value_name = "STEPS",
+        requires = "from"
+    )]
+    steps: Option<usize>,
+    #[arg(
+        long = "direction",
+        help = "side of the primary that triad, tetrad, rectangle, analogous, split-complementary, compound, `--rotations`, `--rotate`, and `--polyad` colors come from (default: both)",
+        value_name = "DIRECTION"
+    )]
+    cli_direction: Option<CliDirection>,
     #[arg(
         short = 'e',
         long = "selector",
         help = "css selector under which variables are declared (default: `:root`)",
-        value_name = "CSS SELECTOR"
+        value_name = "CSS SELECTOR",
+        value_parser = parse_selector
     )]
     pub selector: Option<String>,
+    #[arg(
+        short = 'b',
+        long = "brand",
+        help = "brand color to compare the primary color against (hex value or CSS color name)",
+        value_name = "BRAND COLOR",
+        value_parser = parse_color
+    )]
+    pub brand: Option<colorsys::Hsl>,
+    #[arg(
+        long = "brand-threshold",
+        help = "deltaE above which the primary color is reported as off-brand (default: 2.0)",
+        value_name = "DELTA E",
+        requires = "brand"
+    )]
+    brand_threshold: Option<f64>,
+    #[arg(
+        long = "override",
+        help = "replace a variable's value with a color or CSS keyword such as `currentColor`",
+        value_name = "NAME=VALUE",
+        value_parser = parse_override
+    )]
+    overrides: Vec<(String, Override)>,
+    #[arg(
+        long = "exclude",
+        help = "leave a variable out of the output; may be repeated",
+        value_name = "NAME",
+        allow_hyphen_values = true
+    )]
+    excluded: Vec<String>,
+    #[arg(
+        long = "prefix",
+        help = "namespace every variable name, e.g. `brand` for `--brand-primary`",
+        value_name = "PREFIX"
+    )]
+    prefix: Option<String>,
+    #[arg(
+        long = "no-primary",
+        help = "omit the `--primary` variable from the output"
+    )]
+    no_primary: bool,
+    #[arg(
+        long = "text-pairs",
+        help = "pair every color with a readable black or white `<name>-text` variable"
+    )]
+    text_pairs: bool,
+    #[arg(
+        short = 'f',
+        long = "format",
+        help = "how colors are written out (default: hex)",
+        value_name = "FORMAT"
+    )]
+    cli_format: Option<CliFormat>,
+    #[arg(
+        long = "output-format",
+        help = "kind of document to generate (default: css)",
+        value_name = "OUTPUT FORMAT"
+    )]
+    cli_output_format: Option<CliOutputFormat>,
+    #[arg(
+        long = "keep-360",
+        help = "emit a hue of exactly 360 as `360` rather than `0` in hsl output"
+    )]
+    keep_360: bool,
+    #[arg(
+        long = "minify",
+        help = "write css output on a single line with no optional whitespace"
+    )]
+    minify: bool,
+    #[arg(
+        long = "color-names",
+        help = "annotate each css variable with a comment naming the closest CSS color"
+    )]
+    color_names: bool,
+    #[arg(
+        long = "high-contrast",
+        help = "add a `prefers-contrast: more` block pushing text and background colors apart"
+    )]
+    high_contrast: bool,
+    #[arg(
+        long = "dark-mode",
+        help = "add a `prefers-color-scheme: dark` block with every color's lightness inverted"
+    )]
+    dark_mode: bool,
+    #[arg(
+        long = "order",
+        help = "order variables are written out in (default: insertion)",
+        value_name = "ORDER"
+    )]
+    cli_order: Option<CliOrder>,
+    #[arg(
+        long = "sort",
+        help = "shorthand for `--order alphabetical`",
+        conflicts_with = "cli_order"
+    )]
+    sort: bool,
+    #[arg(
+        long = "canonical-order",
+        help = "shorthand for `--order canonical`: same-hue colors, then hue rotations, then text and background colors",
+        conflicts_with_all = ["cli_order", "sort"]
+    )]
+    canonical_order: bool,
+    #[arg(
+        long = "theme",
+        help = "generate a complete theme: neutrals, text, and background colors plus a dark mode"
+    )]
+    theme: bool,
+    #[arg(
+        long = "contrast-standard",
+        help = "warn about text colors that aren't readable on their background under this standard",
+        value_name = "STANDARD"
+    )]
+    cli_contrast_standard: Option<CliContrastStandard>,
+    #[arg(
+        short = 'o',
+        long = "output",
+        help = "file to write the output to instead of stdout; created or truncated",
+        value_name = "PATH"
+    )]
+    pub output: Option<std::path::PathBuf>,
+    #[arg(
+        long = "config",
+        help = "TOML file giving `primary`, `schemes`, `selector`, and `prefix`; flags take precedence",
+        value_name = "PATH"
+    )]
+    config: Option<std::path::PathBuf>,
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        help = "print notices, e.g. about precision lost converting the primary color, to stderr"
+    )]
+    pub verbose: bool,
+    #[arg(long = "stats", help = "print palette statistics to stderr")]
+    pub stats: bool,
+}
+
+/// cli subcommands
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// convert a single color to another format without generating a scheme
+    Convert(ConvertArgs),
+    /// show how each color of a scheme is derived from the primary
+    Explain(ExplainArgs),
+}
+
+/// arguments to the `explain` subcommand
+#[derive(Debug, ClapArgs)]
+pub struct ExplainArgs {
+    #[arg(
+        short,
+        long = "primary",
+        help = "primary scheme color (hex value or CSS color name)",
+        value_name = "PRIMARY COLOR",
+        value_parser = parse_color
+    )]
+    primary: colorsys::Hsl,
+    #[arg(
+        short = 's',
+        long = "scheme",
+        help = "color schemes to explain",
+        value_name = "SCHEME",
+        required = true
+    )]
+    cli_schemes: Vec<CliScheme>,
+}
+
+impl ExplainArgs {
+    /// one line per generated color
+    pub fn explain(&self) -> String {
+        let schemes = self
+            .cli_schemes
+            .iter()
+            .flat_map(CliScheme::expand)
+            .map(Args::to_scheme);
+        ColorScheme::from_schemes(self.primary.clone(), schemes)
+            .explain()
+            .join("\n")
+    }
+}
+
+/// arguments to the `convert` subcommand
+#[derive(Debug, ClapArgs)]
+pub struct ConvertArgs {
+    #[arg(
+        short,
+        long = "primary",
+        help = "color to convert (hex value or CSS color name)",
+        value_name = "PRIMARY COLOR",
+        value_parser = parse_color
+    )]
+    primary: colorsys::Hsl,
+    #[arg(
+        short = 'f',
+        long = "format",
+        help = "format to convert to (default: hex)",
+        value_name = "FORMAT"
+    )]
+    cli_format: Option<CliFormat>,
+}
+
+impl ConvertArgs {
+    /// the color written out in the requested format
+    pub fn convert(&self) -> String {
+        let format = self
+            .cli_format
+            .as_ref()
+            .map_or(Format::Hex, Args::to_format);
+        format_color(&self.primary, format)
+    }
 }
 
 impl Args {
     /// try to parse the primary color string as either a hex string or [named CSS color](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color)
-    pub fn primary(&self) -> Option<colorsys::Hsl> {
-        Args::parse_primary(&self.primary_str)
+    ///
+    /// `--primary-hsl` has already been validated by the time this is called
+    pub fn primary(&self) -> Result<colorsys::Hsl, ColorParseError> {
+        match (&self.primary_hsl, &self.primary_str) {
+            (Some(hsl), _) => Ok(hsl.clone()),
+            (None, Some(primary)) => crate::parse::parse_color(primary),
+            (None, None) => Err(ColorParseError::EmptyInput),
+        }
+    }
+    /// fill in whatever `--config` gives that no flag did
+    pub fn with_config(mut self) -> Result<Self, String> {
+        let Some(path) = &self.config else {
+            return Ok(self);
+        };
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        let config =
+            config::Config::parse(&input).map_err(|e| format!("{}: {}", path.display(), e))?;
+        self.apply_config(config);
+        Ok(self)
+    }
+    fn apply_config(&mut self, config: config::Config) {
+        if self.primary_str.is_none() && self.primary_hsl.is_none() && self.from.is_none() {
+            self.primary_str = config.primary;
+        }
+        if self.cli_schemes.is_empty() {
+            self.cli_schemes = config.schemes;
+        }
+        self.selector = self.selector.take().or(config.selector);
+        self.prefix = self.prefix.take().or(config.prefix);
+    }
+    /// deltaE above which the primary no longer matches [`Args::brand`]
+    pub fn brand_threshold(&self) -> f64 {
+        self.brand_threshold
+            .unwrap_or(crate::lab::DEFAULT_DELTA_E_THRESHOLD)
     }
     pub fn schemes(&self) -> Vec<Scheme> {
-        self.cli_schemes.iter().map(Args::to_scheme).collect()
+        let mut schemes: Vec<Scheme> = self
+            .cli_schemes
+            .iter()
+            .flat_map(CliScheme::expand)
+            .map(|cli_scheme| match (cli_scheme, self.column_step) {
+                (CliScheme::Column, Some(step)) => Scheme::SteppedColumn(step),
+                (CliScheme::Column, None) if self.perceptual => Scheme::PerceptualColumn,
+                (cli_scheme, _) => Args::to_scheme(cli_scheme),
+            })
+            .collect();
+        if !self.rotations.is_empty() {
+            schemes.push(Scheme::from_degrees(self.rotations.iter().copied()));
+        }
+        schemes.extend(self.rotate.iter().copied().map(Scheme::Rotated));
+        if let Some(steps) = self.monochromatic {
+            schemes.push(if self.perceptual {
+                Scheme::PerceptualMonochromatic(steps)
+            } else {
+                Scheme::Monochromatic(steps)
+            });
+        }
+        if let Some(n) = self.polyad {
+            schemes.push(Scheme::Polyad(n));
+        }
+        if self.theme {
+            schemes.extend([Scheme::Neutrals, Scheme::Text, Scheme::Background]);
+        }
+        schemes
+    }
+    /// the requested schemes for `primary`, with every output option applied
+    pub fn color_scheme(&self, primary: colorsys::Hsl) -> ColorScheme {
+        let direction = match self.cli_direction {
+            None | Some(CliDirection::Both) => Direction::Both,
+            Some(CliDirection::Cw) => Direction::Clockwise,
+            Some(CliDirection::Ccw) => Direction::Counterclockwise,
+        };
+        self.with_options(ColorScheme::from_schemes_in(
+            primary,
+            self.schemes(),
+            direction,
+        ))
+    }
+    /// the `--from`/`--to` ramp, if one was requested, with every output option applied
+    pub fn ramp(&self) -> Option<ColorScheme> {
+        let (from, to) = (self.from.clone()?, self.to.clone()?);
+        let steps = self.steps.unwrap_or(5);
+        Some(self.with_options(ColorScheme::between(from, to, steps)))
+    }
+    fn with_options(&self, mut scheme: ColorScheme) -> ColorScheme {
+        for (name, value) in self.overrides.iter() {
+            scheme = scheme.with_override(name, value.clone());
+        }
+        for name in self.excluded.iter() {
+            scheme = scheme.excluding(name);
+        }
+        if let Some(prefix) = &self.prefix {
+            scheme = scheme.with_prefix(prefix);
+        }
+        if self.no_primary {
+            scheme = scheme.without_primary();
+        }
+        if self.text_pairs {
+            scheme = scheme.with_text_pairs();
+        }
+        if let Some(cli_format) = &self.cli_format {
+            scheme = scheme.with_format(Args::to_format(cli_format));
+        }
+        if self.keep_360 {
+            scheme = scheme.keeping_360();
+        }
+        if self.high_contrast {
+            scheme = scheme.with_high_contrast();
+        }
+        if self.theme || self.dark_mode {
+            scheme = scheme.with_dark_mode();
+        }
+        if let Some(cli_order) = &self.cli_order {
+            scheme = scheme.ordered_by(Args::to_order(cli_order));
+        }
+        if self.sort {
+            scheme = scheme.sorted();
+        }
+        if self.canonical_order {
+            scheme = scheme.canonically_ordered();
+        }
+        if self.color_names {
+            scheme = scheme.with_color_names();
+        }
+        scheme
+    }
+    /// an error for flags that would otherwise be silently ignored, such as
+    /// `--column-step` without the column scheme or `--perceptual` with nothing
+    /// to space out
+    pub fn validate(&self) -> Result<(), String> {
+        let has_column = self
+            .cli_schemes
+            .iter()
+            .flat_map(CliScheme::expand)
+            .any(|cli_scheme| *cli_scheme == CliScheme::Column);
+        if self.column_step.is_some() && !has_column {
+            return Err(String::from(
+                "`--column-step` only applies to the column scheme; add `-s column`",
+            ));
+        }
+        if self.perceptual && !has_column && self.monochromatic.is_none() {
+            return Err(String::from(
+                "`--perceptual` only applies to the column scheme and `--monochromatic`",
+            ));
+        }
+        Ok(())
+    }
+    /// whether `--primary -` asked for colors to be read from stdin
+    pub fn reads_stdin(&self) -> bool {
+        self.primary_str.as_deref() == Some("-")
+    }
+    /// an error if `--primary -` is combined with an [`OutputFormat`] that
+    /// ignores the selector, leaving nothing to tell each line's scheme apart
+    pub fn validate_batch(&self) -> Result<(), String> {
+        match (self.output_format(), &self.cli_output_format) {
+            (OutputFormat::Css | OutputFormat::LightDark, _) | (_, None) => Ok(()),
+            (_, Some(cli_output_format)) => Err(format!(
+                "`--output-format {}` ignores the selector, so it can't be combined with `--primary -`",
+                cli_output_format.to_possible_value().unwrap().get_name()
+            )),
+        }
+    }
+    /// render `scheme` in the requested [`OutputFormat`]
+    pub fn render(&self, scheme: &ColorScheme) -> String {
+        self.render_under(scheme, self.selector.as_deref())
+    }
+    /// render `scheme` in the requested [`OutputFormat`] under `selector`
+    /// instead of `--selector`
+    pub fn render_under(&self, scheme: &ColorScheme, selector: Option<&str>) -> String {
+        match self.output_format() {
+            OutputFormat::Css if self.minify => scheme.as_css_minified(selector),
+            OutputFormat::Css => scheme.as_css(selector),
+            OutputFormat::LightDark => scheme.as_light_dark(selector),
+            OutputFormat::TailwindTheme => scheme.as_tailwind_theme(),
+            OutputFormat::TailwindConfig => scheme.as_tailwind(),
+            OutputFormat::Json => scheme.as_json(),
+            OutputFormat::Scss => scheme.as_scss(),
+            OutputFormat::Html => scheme.as_html_preview(),
+        }
+    }
+    /// a notice if the primary can't be written out exactly in the requested [`Format`]
+    pub fn precision_loss(&self, primary: &colorsys::Hsl) -> Option<String> {
+        let format = self
+            .cli_format
+            .as_ref()
+            .map_or(Format::Hex, Args::to_format);
+        precision_loss(primary, format)
+    }
+    /// the requested [`ContrastStandard`], if any
+    pub fn contrast_standard(&self) -> Option<ContrastStandard> {
+        self.cli_contrast_standard
+            .as_ref()
+            .map(|standard| match standard {
+                CliContrastStandard::Aa => ContrastStandard::Aa,
+                CliContrastStandard::Aaa => ContrastStandard::Aaa,
+                CliContrastStandard::Apca => ContrastStandard::Apca,
+            })
+    }
+    fn output_format(&self) -> OutputFormat {
+        match self.cli_output_format {
+            None | Some(CliOutputFormat::Css) => OutputFormat::Css,
+            Some(CliOutputFormat::LightDark) => OutputFormat::LightDark,
+            Some(CliOutputFormat::TailwindTheme) => OutputFormat::TailwindTheme,
+            Some(CliOutputFormat::TailwindConfig) => OutputFormat::TailwindConfig,
+            Some(CliOutputFormat::Json) => OutputFormat::Json,
+            Some(CliOutputFormat::Scss) => OutputFormat::Scss,
+            Some(CliOutputFormat::Html) => OutputFormat::Html,
+        }
+    }
+    /// convert from [`CliFormat`] to [`Format`]
+    fn to_format(cli_format: &CliFormat) -> Format {
+        match cli_format {
+            CliFormat::Hex => Format::Hex,
+            CliFormat::Rgb => Format::Rgb,
+            CliFormat::Hsl => Format::Hsl,
+            CliFormat::Oklch => Format::Oklch,
+        }
+    }
+    /// convert from [`CliOrder`] to [`Order`]
+    fn to_order(cli_order: &CliOrder) -> Order {
+        match cli_order {
+            CliOrder::Insertion => Order::Insertion,
+            CliOrder::Luminance => Order::Luminance,
+            CliOrder::Alphabetical => Order::Alphabetical,
+            CliOrder::Canonical => Order::Canonical,
+        }
     }
     /// convert from [`CliScheme`] to [`Scheme`]
     fn to_scheme(cli_scheme: &CliScheme) -> Scheme {
@@ -74,35 +721,597 @@ impl Args {
             CliScheme::DiagonalComplementary => Scheme::DiagonalComplementary,
             CliScheme::Triad => Scheme::Triad,
             CliScheme::Tetrad => Scheme::Tetrad,
+            CliScheme::Rectangle => Scheme::Rectangle,
+            CliScheme::Analogous => Scheme::Analogous,
+            CliScheme::SplitComplementary => Scheme::SplitComplementary,
+            CliScheme::Compound => Scheme::Compound,
             CliScheme::Text => Scheme::Text,
             CliScheme::Background => Scheme::Background,
+            CliScheme::Neutrals => Scheme::Neutrals,
+            CliScheme::Pastel => Scheme::Pastel,
+            CliScheme::Ramp => Scheme::Ramp,
+            CliScheme::Grayscale => Scheme::Grayscale,
+            CliScheme::Alpha => Scheme::Alpha,
+            CliScheme::All => unreachable!("`all` is expanded by `CliScheme::expand`"),
         }
     }
-    /// try to parse the provided input as either a hex string or CSS color name
-    fn parse_primary(primary: &str) -> Option<colorsys::Hsl> {
-        if let Some('#') = primary.chars().nth(0) {
-            // hex string
-            colorsys::Rgb::from_hex_str(primary).map(|c| c.into()).ok()
-        } else if primary == NamedColor::TRANSPARENT.name() {
-            // 'transparent' is a valid CSS color name but not useful to us
-            None
-        } else {
-            // color name?
-            let from_name = if let Some(nc) = NamedColor::from_name(primary) {
-                // safety: we know `nc` is not `TRANSPARENT`
-                let (r, g, b) = nc.rgb().unwrap();
-                Some(colorsys::Rgb::new(r.into(), g.into(), b.into(), None).into())
-            } else {
-                None
-            };
-            if from_name.is_some() {
-                from_name
-            } else {
-                // hex without the hash?
-                colorsys::Rgb::from_hex_str(&format!("#{}", primary))
-                    .map(|c| c.into())
-                    .ok()
-            }
+}
+
+/// the colors in `input`, one per line, for `--primary -`
+///
+/// blank lines are skipped; each color comes with its 1-based line number
+pub fn read_primaries(
+    input: impl std::io::BufRead,
+) -> std::io::Result<Vec<(usize, Result<colorsys::Hsl, ColorParseError>)>> {
+    let mut primaries = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            primaries.push((i + 1, crate::parse::parse_color(&line)));
+        }
+    }
+    Ok(primaries)
+}
+
+/// [`crate::parse::parse_color`] adapted for use as a clap value parser
+fn parse_color(s: &str) -> Result<colorsys::Hsl, String> {
+    crate::parse::parse_color(s).map_err(|e| e.to_string())
+}
+
+/// a trimmed `--selector`, which can't contain braces since it's written out
+/// ahead of a `{` block
+fn parse_selector(s: &str) -> Result<String, String> {
+    if s.contains(['{', '}']) {
+        return Err(format!("selector `{}` can't contain `{{` or `}}`", s));
+    }
+    Ok(s.trim().to_string())
+}
+
+/// parse an angle in degrees, which has to be a finite number
+fn parse_degrees(s: &str) -> Result<f64, String> {
+    let degrees = s
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number `{}`", s))?;
+    if !degrees.is_finite() {
+        return Err(format!("angle must be a finite number, got {}", degrees));
+    }
+    Ok(degrees)
+}
+
+/// parse a `--column-step`, from 0 to 100 points of lightness with an optional `%`
+fn parse_column_step(s: &str) -> Result<f64, String> {
+    let step = s
+        .strip_suffix('%')
+        .unwrap_or(s)
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number `{}`", s))?;
+    if !(0.0..=100.0).contains(&step) {
+        return Err(format!("step must be between 0 and 100, got {}", step));
+    }
+    Ok(step)
+}
+
+/// parse a `name=value` override, where `value` is a CSS keyword or color
+fn parse_override(s: &str) -> Result<(String, Override), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got `{}`", s))?;
+    let value = match Override::keyword(value) {
+        Some(keyword) => keyword,
+        None => Override::Color(parse_color(value)?),
+    };
+    Ok((name.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_convert() {
+        let args =
+            Args::try_parse_from(["colorscheme", "convert", "-p", "red", "-f", "hsl"]).unwrap();
+        let Some(Command::Convert(convert)) = args.command else {
+            panic!("expected convert subcommand");
+        };
+        assert_eq!(convert.convert(), "hsl(0, 100%, 50%)");
+    }
+    #[test]
+    fn test_theme() {
+        let args = Args::try_parse_from(["colorscheme", "-p", "teal", "--theme"]).unwrap();
+        let css = args.color_scheme(args.primary().unwrap()).as_css(None);
+        assert!(css.contains("\n\t--text-primary: "));
+        assert!(css.contains("\n\t--background-primary: "));
+        assert!(css.contains("\n@media (prefers-color-scheme: dark) {"));
+    }
+    #[test]
+    fn test_ramp() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "--from",
+            "#000",
+            "--to",
+            "#fff",
+            "--steps",
+            "3",
+        ])
+        .unwrap();
+        let css = args.ramp().unwrap().as_css(None);
+        assert!(css.contains("\n\t--step-0: #000000;"));
+        assert!(css.contains("\n\t--step-1: #808080;"));
+        assert!(css.contains("\n\t--step-2: #ffffff;"));
+        assert!(Args::try_parse_from(["colorscheme", "--from", "#000"]).is_err());
+    }
+    #[test]
+    fn test_explain() {
+        let args =
+            Args::try_parse_from(["colorscheme", "explain", "-p", "red", "-s", "triad"]).unwrap();
+        let Some(Command::Explain(explain)) = args.command else {
+            panic!("expected the explain subcommand");
+        };
+        let explanation = explain.explain();
+        assert!(explanation.contains("+120"));
+        assert!(explanation.contains("-120"));
+    }
+    #[test]
+    fn test_exclude() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "tetrad",
+            "--exclude",
+            "--lower-left",
+        ])
+        .unwrap();
+        let css = args.color_scheme(args.primary().unwrap()).as_css(None);
+        assert!(!css.contains("--lower-left"));
+        assert!(css.contains("--lower-right"));
+    }
+    #[test]
+    fn test_selector() {
+        let parse = |selector| {
+            Args::try_parse_from(["colorscheme", "-p", "red", "-s", "triad", "-e", selector])
+        };
+        assert_eq!(
+            parse("  .theme ").unwrap().selector.as_deref(),
+            Some(".theme")
+        );
+        let blank = parse(" ").unwrap();
+        assert!(
+            blank
+                .render(&blank.color_scheme(blank.primary().unwrap()))
+                .starts_with(":root {")
+        );
+        assert!(parse(".theme { color: red; }").is_err());
+    }
+    #[test]
+    fn test_column_step() {
+        let args = Args::try_parse_from(["colorscheme", "-p", "red", "-s", "column"]).unwrap();
+        assert_eq!(args.schemes(), vec![Scheme::Column]);
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "column",
+            "-s",
+            "triad",
+            "--column-step",
+            "10%",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.schemes(),
+            vec![Scheme::SteppedColumn(10.0), Scheme::Triad]
+        );
+        assert_eq!(args.validate(), Ok(()));
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "--column-step",
+            "10",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.validate(),
+            Err(String::from(
+                "`--column-step` only applies to the column scheme; add `-s column`"
+            ))
+        );
+        assert!(
+            Args::try_parse_from([
+                "colorscheme",
+                "-p",
+                "red",
+                "-s",
+                "column",
+                "--column-step",
+                "101"
+            ])
+            .is_err()
+        );
+    }
+    #[test]
+    fn test_perceptual() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "column",
+            "--monochromatic",
+            "3",
+            "--perceptual",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.schemes(),
+            vec![Scheme::PerceptualColumn, Scheme::PerceptualMonochromatic(3)]
+        );
+        assert_eq!(args.validate(), Ok(()));
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "--monochromatic",
+            "3",
+            "--perceptual",
+        ];
+        assert_eq!(Args::try_parse_from(argv).unwrap().validate(), Ok(()));
+        let argv = ["colorscheme", "-p", "red", "-s", "triad", "--perceptual"];
+        assert_eq!(
+            Args::try_parse_from(argv).unwrap().validate(),
+            Err(String::from(
+                "`--perceptual` only applies to the column scheme and `--monochromatic`"
+            ))
+        );
+    }
+    #[test]
+    fn test_rotate() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "--rotate",
+            "450",
+            "--rotate",
+            "-30",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.schemes(),
+            vec![Scheme::Rotated(450.0), Scheme::Rotated(-30.0)]
+        );
+        let css = args.color_scheme(args.primary().unwrap()).as_css(None);
+        assert!(css.contains("\n\t--rotated-90: #80ff00;"));
+        assert!(css.contains("\n\t--rotated-330: "));
+        for degrees in ["NaN", "inf", "-inf"] {
+            let argv = ["colorscheme", "-p", "red", "--rotate", degrees];
+            assert!(Args::try_parse_from(argv).is_err());
+            let rotations = format!("--rotations=30,{}", degrees);
+            let argv = ["colorscheme", "-p", "red", rotations.as_str()];
+            assert!(Args::try_parse_from(argv).is_err());
+        }
+    }
+    #[test]
+    fn test_direction() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "--direction",
+            "cw",
+        ])
+        .unwrap();
+        let css = args.color_scheme(args.primary().unwrap()).as_css(None);
+        assert!(css.contains("\n\t--clockwise: "));
+        assert!(!css.contains("--counterclockwise"));
+    }
+    #[test]
+    fn test_direction_help() {
+        use clap::CommandFactory;
+        let command = Args::command();
+        let help = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == "cli_direction")
+            .and_then(|arg| arg.get_help())
+            .unwrap()
+            .to_string();
+        for cli_scheme in CliScheme::All.expand() {
+            let scheme = Args::to_scheme(cli_scheme);
+            let name = cli_scheme
+                .to_possible_value()
+                .unwrap()
+                .get_name()
+                .to_string();
+            // whole words, since `split-complementary` contains `complementary`
+            let mentioned = help.split([' ', ',']).any(|word| word == name);
+            assert_eq!(mentioned, scheme.is_directional(), "{}", name);
+        }
+        for (flag, scheme) in [
+            ("`--rotations`", Scheme::from_degrees([30.0])),
+            ("`--rotate`", Scheme::Rotated(30.0)),
+            ("`--polyad`", Scheme::Polyad(5)),
+        ] {
+            assert!(scheme.is_directional());
+            assert!(help.contains(flag));
         }
     }
+    #[test]
+    fn test_diagonal_complementary() {
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "#4d6633",
+            "-s",
+            "diagonal-complementary",
+        ])
+        .unwrap();
+        let primary = args.primary().unwrap();
+        let scheme = args.color_scheme(primary.clone());
+        let color = scheme.get("--diagonal-complementary").unwrap();
+        // rotated hues are rounded to hundredths of a degree
+        let complementary = crate::Hue::new(primary.hue()) + 180.0;
+        assert!(crate::Hue::new(color.hue()).approx_eq(complementary, 0.005));
+        assert!((color.saturation() - (100.0 - primary.saturation())).abs() < 1e-9);
+        assert!((color.lightness() - (100.0 - primary.lightness())).abs() < 1e-9);
+    }
+    #[test]
+    fn test_translucent_primary_round_trip() {
+        let args = Args::try_parse_from(["colorscheme", "-p", "#ff000080", "-s", "complementary"])
+            .unwrap();
+        let css = args.color_scheme(args.primary().unwrap()).as_css(None);
+        assert_eq!(
+            css,
+            ":root {\n\t--primary: #ff000080;\n\t--complementary: #00ffff80;\n}"
+        );
+    }
+    #[test]
+    fn test_sort() {
+        let css = |extra: &[&str]| {
+            let mut argv = vec![
+                "colorscheme",
+                "-p",
+                "red",
+                "-s",
+                "triad",
+                "-s",
+                "complementary",
+            ];
+            argv.extend(extra);
+            let args = Args::try_parse_from(argv).unwrap();
+            args.render(&args.color_scheme(args.primary().unwrap()))
+        };
+        let sorted = css(&["--sort"]);
+        assert_ne!(sorted, css(&[]));
+        assert_eq!(sorted, css(&["--order", "alphabetical"]));
+        assert!(sorted.find("--complementary") < sorted.find("--counterclockwise"));
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "--sort",
+            "--order",
+            "luminance",
+        ];
+        assert!(Args::try_parse_from(argv).is_err());
+    }
+    #[test]
+    fn test_text_pairs_without_primary() {
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "--text-pairs",
+            "--no-primary",
+        ];
+        let args = Args::try_parse_from(argv).unwrap();
+        let css = args.render(&args.color_scheme(args.primary().unwrap()));
+        assert!(!css.contains("--primary"));
+        assert!(css.contains("--clockwise-text"));
+    }
+    #[test]
+    fn test_no_primary_other_formats() {
+        let render = |output_format: &str| {
+            let argv = [
+                "colorscheme",
+                "-p",
+                "red",
+                "-s",
+                "complementary",
+                "--no-primary",
+                "--output-format",
+                output_format,
+            ];
+            let args = Args::try_parse_from(argv).unwrap();
+            args.render(&args.color_scheme(args.primary().unwrap()))
+        };
+        assert_eq!(render("json"), r##"{"--complementary":"#00ffff"}"##);
+        assert_eq!(render("scss"), "$complementary: #00ffff;");
+    }
+    #[test]
+    fn test_canonical_order() {
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "text",
+            "-s",
+            "triad",
+            "-s",
+            "background",
+            "-s",
+            "column",
+            "--canonical-order",
+        ];
+        let args = Args::try_parse_from(argv).unwrap();
+        let css = args.render(&args.color_scheme(args.primary().unwrap()));
+        let names: Vec<&str> = css
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("--")?.split_once(':'))
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "primary",
+                "lighter",
+                "darker",
+                "clockwise",
+                "counterclockwise",
+                "text-primary",
+                "background-primary",
+            ]
+        );
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "--sort",
+            "--canonical-order",
+        ];
+        assert!(Args::try_parse_from(argv).is_err());
+    }
+    #[test]
+    fn test_config() {
+        let fixture = "primary = \"#f0af0a\"\nschemes = [\"triad\", \"column\"]\nselector = \".theme\"\nprefix = \"brand\"\n";
+        let config = config::Config::parse(fixture).unwrap();
+        let mut from_config =
+            Args::try_parse_from(["colorscheme", "--config", "theme.toml"]).unwrap();
+        from_config.apply_config(config);
+        let from_flags = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "#f0af0a",
+            "-s",
+            "triad",
+            "-s",
+            "column",
+            "-e",
+            ".theme",
+            "--prefix",
+            "brand",
+        ])
+        .unwrap();
+        let scheme = |args: &Args| args.color_scheme(args.primary().unwrap());
+        assert_eq!(scheme(&from_config), scheme(&from_flags));
+        assert_eq!(from_config.selector.as_deref(), Some(".theme"));
+        let mut overridden = Args::try_parse_from([
+            "colorscheme",
+            "--config",
+            "theme.toml",
+            "-p",
+            "red",
+            "-e",
+            ".other",
+        ])
+        .unwrap();
+        overridden.apply_config(config::Config::parse(fixture).unwrap());
+        assert_eq!(overridden.primary_str.as_deref(), Some("red"));
+        assert_eq!(overridden.selector.as_deref(), Some(".other"));
+        assert_eq!(
+            overridden.cli_schemes,
+            vec![CliScheme::Triad, CliScheme::Column]
+        );
+        let missing = Args::try_parse_from(["colorscheme", "--config", "/nonexistent.toml"])
+            .unwrap()
+            .with_config();
+        assert!(
+            missing
+                .unwrap_err()
+                .starts_with("couldn't read /nonexistent.toml")
+        );
+    }
+    #[test]
+    fn test_all_schemes() {
+        let args = Args::try_parse_from(["colorscheme", "-p", "red", "-s", "all"]).unwrap();
+        assert_eq!(args.schemes().len(), CliScheme::value_variants().len() - 1);
+        let scheme = args.color_scheme(args.primary().unwrap());
+        for name in [
+            "--complementary",
+            "--clockwise",
+            "--upper-right",
+            "--text-primary",
+            "--background-primary",
+        ] {
+            assert!(scheme.get(name).is_some(), "missing {}", name);
+        }
+        let css = scheme.as_css(None);
+        assert_eq!(css.matches("\t--complementary:").count(), 1);
+    }
+    #[test]
+    fn test_scheme_names_match() {
+        for cli_scheme in CliScheme::All.expand() {
+            let value = cli_scheme.to_possible_value().unwrap();
+            assert_eq!(value.get_name().parse(), Ok(Args::to_scheme(cli_scheme)));
+        }
+    }
+    #[test]
+    fn test_brand_parse_error() {
+        // clap reports the `ColorParseError` for flags parsed as colors
+        let argv = [
+            "colorscheme",
+            "-p",
+            "red",
+            "-s",
+            "triad",
+            "-b",
+            "transparent",
+        ];
+        let error = Args::try_parse_from(argv).unwrap_err().to_string();
+        assert!(error.contains(
+            "invalid value 'transparent' for '--brand <BRAND COLOR>': `transparent` has no hue to build a scheme from"
+        ));
+    }
+    #[test]
+    fn test_read_primaries() {
+        let input = "red\n\n  \nbleu\n#00f\n";
+        let primaries = read_primaries(input.as_bytes()).unwrap();
+        assert_eq!(
+            primaries,
+            vec![
+                (1, Ok(colorsys::Hsl::new(0.0, 100.0, 50.0, None))),
+                (
+                    4,
+                    Err(ColorParseError::UnknownColorName(
+                        String::from("bleu"),
+                        Some("blue")
+                    ))
+                ),
+                (5, Ok(colorsys::Hsl::new(240.0, 100.0, 50.0, None))),
+            ]
+        );
+        let args = Args::try_parse_from(["colorscheme", "-p", "-", "-s", "triad"]).unwrap();
+        assert!(args.reads_stdin());
+        assert_eq!(args.validate_batch(), Ok(()));
+        let args = Args::try_parse_from([
+            "colorscheme",
+            "-p",
+            "-",
+            "-s",
+            "triad",
+            "--output-format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.validate_batch(),
+            Err(String::from(
+                "`--output-format json` ignores the selector, so it can't be combined with `--primary -`"
+            ))
+        );
+    }
 }
@@ -0,0 +1,199 @@
+//! # `--config` files
+//!
+//! a small subset of [TOML](https://toml.io): top-level `key = value` pairs,
+//! where values are strings or arrays of strings, and `#` comments
+use super::CliScheme;
+use clap::ValueEnum;
+
+/// the settings a config file can give, each of which a command-line flag overrides
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Config {
+    pub(crate) primary: Option<String>,
+    pub(crate) schemes: Vec<CliScheme>,
+    pub(crate) selector: Option<String>,
+    pub(crate) prefix: Option<String>,
+}
+
+impl Config {
+    /// parse a config file's contents
+    ///
+    /// the primary is checked with [`crate::parse::parse_color`] and schemes go by
+    /// their `--scheme` names; unknown keys are an error
+    pub(crate) fn parse(input: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+        let mut lines = input.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let at_line = |e: String| format!("line {}: {}", i + 1, e);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| at_line(format!("expected `key = value`, got `{}`", line)))?;
+            let mut value = value.trim().to_string();
+            // arrays can span several lines
+            if value.starts_with('[') {
+                while !value.ends_with(']') {
+                    let (_, next) = lines
+                        .next()
+                        .ok_or_else(|| at_line(String::from("unclosed `[` array")))?;
+                    value.push(' ');
+                    value.push_str(strip_comment(next).trim());
+                }
+            }
+            match key.trim() {
+                "primary" => {
+                    let primary = parse_string(&value).map_err(at_line)?;
+                    crate::parse::parse_color(&primary)
+                        .map_err(|e| at_line(format!("primary: {}", e)))?;
+                    config.primary = Some(primary);
+                }
+                "schemes" => {
+                    config.schemes = parse_array(&value)
+                        .map_err(at_line)?
+                        .iter()
+                        .map(|name| {
+                            CliScheme::from_str(name, true)
+                                .map_err(|_| at_line(format!("unknown scheme `{}`", name)))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "selector" => {
+                    let selector = parse_string(&value).map_err(at_line)?;
+                    config.selector = Some(super::parse_selector(&selector).map_err(at_line)?);
+                }
+                "prefix" => config.prefix = Some(parse_string(&value).map_err(at_line)?),
+                key => return Err(at_line(format!("unknown key `{}`", key))),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// `line` up to a `#` that isn't inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// a `"basic"` string, with `\"` and `\\` escapes, or a `'literal'` one
+fn parse_string(value: &str) -> Result<String, String> {
+    if let Some(literal) = value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .filter(|v| !v.contains('\''))
+    {
+        return Ok(literal.to_string());
+    }
+    let invalid = || format!("expected a quoted string, got `{}`", value);
+    let basic = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    let mut s = String::new();
+    let mut chars = basic.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => s.push(escaped),
+                _ => return Err(format!("unsupported escape in `{}`", value)),
+            },
+            '"' => return Err(invalid()),
+            c => s.push(c),
+        }
+    }
+    Ok(s)
+}
+
+/// an array of strings, e.g. `["triad", "column"]`, with an optional trailing comma
+fn parse_array(value: &str) -> Result<Vec<String>, String> {
+    let items = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| format!("expected an array of strings, got `{}`", value))?;
+    // scheme names don't contain commas, so splitting on them is enough
+    items
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            r##"
+            # brand palette
+            primary = "#f0af0a"
+            schemes = [
+                "triad", # the main accents
+                'column',
+            ]
+            selector = ".theme"
+            prefix = 'brand'
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                primary: Some(String::from("#f0af0a")),
+                schemes: vec![CliScheme::Triad, CliScheme::Column],
+                selector: Some(String::from(".theme")),
+                prefix: Some(String::from("brand")),
+            }
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+    #[test]
+    fn test_parse_config_errors() {
+        let error = |input| Config::parse(input).unwrap_err();
+        assert_eq!(
+            error("primary = \"nope\""),
+            "line 1: primary: `nope` isn't a CSS color name or hex color"
+        );
+        assert_eq!(
+            error("\nschemes = [\"hexad\"]"),
+            "line 2: unknown scheme `hexad`"
+        );
+        assert_eq!(error("format = \"hex\""), "line 1: unknown key `format`");
+        assert_eq!(
+            error("primary = red"),
+            "line 1: expected a quoted string, got `red`"
+        );
+        assert_eq!(error("schemes = [\"triad\""), "line 1: unclosed `[` array");
+        assert_eq!(
+            error("[colors]"),
+            "line 1: expected `key = value`, got `[colors]`"
+        );
+    }
+    #[test]
+    fn test_strip_comment() {
+        assert_eq!(
+            strip_comment("primary = \"#fff\" # white"),
+            "primary = \"#fff\" "
+        );
+        let escaped = r##"prefix = "a\"#b""##;
+        assert_eq!(strip_comment(escaped), escaped);
+        assert_eq!(strip_comment("# comment"), "");
+    }
+}
@@ -0,0 +1,57 @@
+//! # colors with cached conversions
+//!
+//! schemes are built from [`Hsl`] colors, but serializing and comparing them
+//! goes through RGB; [`Color`] converts once and keeps the results around
+use crate::contrast::rgb_luminance;
+use colorsys::{Hsl, Rgb};
+
+/// an [`Hsl`] color along with its [`Rgb`] equivalent and relative luminance
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Color {
+    hsl: Hsl,
+    rgb: Rgb,
+    luminance: f64,
+}
+
+impl Color {
+    pub(crate) fn hsl(&self) -> &Hsl {
+        &self.hsl
+    }
+    pub(crate) fn rgb(&self) -> &Rgb {
+        &self.rgb
+    }
+    /// see [`crate::contrast::relative_luminance`]
+    pub(crate) fn luminance(&self) -> f64 {
+        self.luminance
+    }
+}
+
+impl From<&Hsl> for Color {
+    fn from(hsl: &Hsl) -> Self {
+        let rgb = Rgb::from(hsl);
+        let luminance = rgb_luminance(&rgb);
+        Color {
+            hsl: hsl.clone(),
+            rgb,
+            luminance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contrast::relative_luminance;
+    #[test]
+    fn test_cached_conversions() {
+        for hue in (0..360).step_by(15) {
+            for lightness in [0.0, 12.5, 50.0, 87.5, 100.0] {
+                let hsl = Hsl::new(hue as f64, 60.0, lightness, Some(0.5));
+                let color = Color::from(&hsl);
+                assert_eq!(color.hsl(), &hsl);
+                assert_eq!(color.rgb(), &Rgb::from(&hsl));
+                assert_eq!(color.luminance(), relative_luminance(&hsl));
+            }
+        }
+    }
+}
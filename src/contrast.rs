@@ -0,0 +1,232 @@
+//! # WCAG and APCA contrast
+//!
+//! luminance and contrast ratios as defined by
+//! [WCAG 2.1](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio), and lightness
+//! contrast as defined by [APCA](https://github.com/Myndex/apca-w3) 0.0.98G
+use crate::lab::linearize;
+use colorsys::{Hsl, Rgb};
+
+/// relative luminance below which a color contrasts more with white than with
+/// black, `sqrt(1.05 * 0.05) - 0.05`
+pub const DARK_LUMINANCE_THRESHOLD: f64 = 0.17912878474779194;
+
+/// relative luminance, from `0.0` (black) to `1.0` (white)
+pub fn relative_luminance(color: &Hsl) -> f64 {
+    rgb_luminance(&Rgb::from(color))
+}
+
+/// whether `color` is dark enough to want white rather than black text
+///
+/// uses the WCAG crossover of [`DARK_LUMINANCE_THRESHOLD`] rather than `0.5`, so it
+/// agrees with [`readable_on`]
+pub fn is_dark(color: &Hsl) -> bool {
+    relative_luminance(color) < DARK_LUMINANCE_THRESHOLD
+}
+
+pub(crate) fn rgb_luminance(rgb: &Rgb) -> f64 {
+    0.2126 * linearize(rgb.red()) + 0.7152 * linearize(rgb.green()) + 0.0722 * linearize(rgb.blue())
+}
+
+/// contrast ratio between two colors, from `1.0` (identical) to `21.0` (black and white)
+///
+/// the order of the arguments doesn't matter
+pub fn contrast_ratio(a: &Hsl, b: &Hsl) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// lightness contrast (Lc) of `text` on `background` under APCA, from about
+/// `106` (black on white) to about `-108` (white on black)
+///
+/// unlike [`contrast_ratio`] the order of the arguments matters: light text on a
+/// dark background is negative
+pub fn apca_contrast(text: &Hsl, background: &Hsl) -> f64 {
+    let text = apca_luminance(text);
+    let background = apca_luminance(background);
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+    let lc = if background > text {
+        let sapc = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+        if sapc < 0.1 { 0.0 } else { sapc - 0.027 }
+    } else {
+        let sapc = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+        if sapc > -0.1 { 0.0 } else { sapc + 0.027 }
+    };
+    lc * 100.0
+}
+
+/// APCA's screen luminance: a simple 2.4 gamma, with a soft clamp near black
+fn apca_luminance(color: &Hsl) -> f64 {
+    let rgb = Rgb::from(color);
+    let y = 0.2126729 * (rgb.red() / 255.0).powf(2.4)
+        + 0.7151522 * (rgb.green() / 255.0).powf(2.4)
+        + 0.0721750 * (rgb.blue() / 255.0).powf(2.4);
+    if y < 0.022 {
+        y + (0.022 - y).powf(1.414)
+    } else {
+        y
+    }
+}
+
+/// the contrast text has to reach against its background to count as readable
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ContrastStandard {
+    /// WCAG 2 AA: a contrast ratio of at least `4.5`
+    Aa,
+    /// WCAG 2 AAA: a contrast ratio of at least `7.0`
+    Aaa,
+    /// APCA: a lightness contrast of at least `75`, in either polarity, the
+    /// level recommended for body text
+    Apca,
+}
+
+impl ContrastStandard {
+    /// the minimum contrast, as a ratio for WCAG or as Lc for APCA
+    pub fn threshold(&self) -> f64 {
+        match self {
+            ContrastStandard::Aa => 4.5,
+            ContrastStandard::Aaa => 7.0,
+            ContrastStandard::Apca => 75.0,
+        }
+    }
+    /// the contrast of `text` on `background` as measured by this standard
+    pub fn contrast(&self, text: &Hsl, background: &Hsl) -> f64 {
+        match self {
+            ContrastStandard::Aa | ContrastStandard::Aaa => contrast_ratio(text, background),
+            ContrastStandard::Apca => apca_contrast(text, background).abs(),
+        }
+    }
+    /// whether `text` is readable on `background`
+    pub fn passes(&self, text: &Hsl, background: &Hsl) -> bool {
+        self.contrast(text, background) >= self.threshold()
+    }
+}
+
+impl std::fmt::Display for ContrastStandard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ContrastStandard::Aa => "WCAG AA",
+            ContrastStandard::Aaa => "WCAG AAA",
+            ContrastStandard::Apca => "APCA",
+        })
+    }
+}
+
+/// black or white, whichever contrasts more with `background`
+pub fn readable_on(background: &Hsl) -> Hsl {
+    let black = Hsl::new(0.0, 0.0, 0.0, None);
+    let white = Hsl::new(0.0, 0.0, 100.0, None);
+    if contrast_ratio(background, &black) >= contrast_ratio(background, &white) {
+        black
+    } else {
+        white
+    }
+}
+
+/// move `text` and `background` apart in lightness, keeping their hues and
+/// saturations, until they contrast by at least `target` or can't be moved further
+///
+/// whichever of the two is darker is darkened and the other lightened, one
+/// percentage point at a time
+pub fn solve_contrast(text: &Hsl, background: &Hsl, target: f64) -> (Hsl, Hsl) {
+    let mut text = text.clone();
+    let mut background = background.clone();
+    let step = if relative_luminance(&text) <= relative_luminance(&background) {
+        1.0
+    } else {
+        -1.0
+    };
+    while contrast_ratio(&text, &background) < target {
+        let text_lightness = (text.lightness() - step).clamp(0.0, 100.0);
+        let background_lightness = (background.lightness() + step).clamp(0.0, 100.0);
+        if text_lightness == text.lightness() && background_lightness == background.lightness() {
+            break;
+        }
+        text.set_lightness(text_lightness);
+        background.set_lightness(background_lightness);
+    }
+    (text, background)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Hsl::new(0.0, 0.0, 0.0, None);
+        let white = Hsl::new(0.0, 0.0, 100.0, None);
+        assert!((contrast_ratio(&black, &white) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(&white, &black) - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio(&white, &white), 1.0);
+    }
+    #[test]
+    fn test_relative_luminance() {
+        assert_eq!(relative_luminance(&Hsl::new(0.0, 0.0, 0.0, None)), 0.0);
+        assert!((relative_luminance(&Hsl::new(0.0, 0.0, 100.0, None)) - 1.0).abs() < 1e-9);
+        // #808080
+        let gray = Hsl::from(Rgb::new(128.0, 128.0, 128.0, None));
+        assert!((relative_luminance(&gray) - 0.21586).abs() < 1e-5);
+    }
+    #[test]
+    fn test_dark_luminance_threshold() {
+        let ratio = |l: f64| (l + 0.05) / 0.05;
+        assert!(
+            (ratio(DARK_LUMINANCE_THRESHOLD) - 1.05 / (DARK_LUMINANCE_THRESHOLD + 0.05)).abs()
+                < 1e-9
+        );
+    }
+    #[test]
+    fn test_is_dark() {
+        assert!(is_dark(&Hsl::new(0.0, 0.0, 0.0, None)));
+        assert!(!is_dark(&Hsl::new(0.0, 0.0, 100.0, None)));
+        assert!(!is_dark(&Hsl::from(Rgb::new(128.0, 128.0, 128.0, None))));
+        assert!(is_dark(&Hsl::from(Rgb::new(100.0, 100.0, 100.0, None))));
+        for lightness in (0..=100).step_by(5) {
+            let color = Hsl::new(210.0, 60.0, f64::from(lightness), None);
+            assert_eq!(is_dark(&color), readable_on(&color).lightness() == 100.0);
+        }
+    }
+    #[test]
+    fn test_readable_on() {
+        let yellow = Hsl::new(60.0, 100.0, 50.0, None);
+        assert_eq!(readable_on(&yellow).lightness(), 0.0);
+        let navy = Hsl::new(240.0, 100.0, 25.0, None);
+        assert_eq!(readable_on(&navy).lightness(), 100.0);
+    }
+    #[test]
+    fn test_solve_contrast() {
+        let text = Hsl::new(200.0, 50.0, 40.0, None);
+        let background = Hsl::new(200.0, 50.0, 60.0, None);
+        let (new_text, new_background) = solve_contrast(&text, &background, 7.0);
+        assert!(contrast_ratio(&new_text, &new_background) >= 7.0);
+        assert_eq!(new_text.hue(), 200.0);
+        assert!(new_text.lightness() < text.lightness());
+        assert!(new_background.lightness() > background.lightness());
+        // can't exceed black on white
+        let (black, white) = solve_contrast(&text, &background, 25.0);
+        assert_eq!((black.lightness(), white.lightness()), (0.0, 100.0));
+    }
+    #[test]
+    fn test_apca_contrast() {
+        let black = Hsl::new(0.0, 0.0, 0.0, None);
+        let white = Hsl::new(0.0, 0.0, 100.0, None);
+        assert!((apca_contrast(&black, &white) - 106.04).abs() < 0.01);
+        assert!((apca_contrast(&white, &black) + 107.88).abs() < 0.01);
+        assert_eq!(apca_contrast(&white, &white), 0.0);
+    }
+    #[test]
+    fn test_contrast_standards() {
+        // #767676 on white is the lightest gray that passes AA, but falls short
+        // of APCA's body text level
+        let gray = Hsl::from(Rgb::new(118.0, 118.0, 118.0, None));
+        let white = Hsl::new(0.0, 0.0, 100.0, None);
+        assert!(ContrastStandard::Aa.passes(&gray, &white));
+        assert!(!ContrastStandard::Aaa.passes(&gray, &white));
+        assert!(!ContrastStandard::Apca.passes(&gray, &white));
+        let black = Hsl::new(0.0, 0.0, 0.0, None);
+        assert!(ContrastStandard::Apca.passes(&black, &white));
+        assert!(ContrastStandard::Apca.passes(&white, &black));
+    }
+}
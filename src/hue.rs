@@ -1,4 +1,4 @@
-//! # [`Hue`] newtype - [`f64`] with modulo 360 addition and subtraction
+//! # [`Hue`] newtype - [`f64`] with modulo 360 arithmetic
 //!
 //! [`colorsys::Hsl`] clamps hue values to be between `0.0` and `360.0`,
 //! so naively adding or subtracting won't do what we want
@@ -21,13 +21,12 @@ impl Hue {
     fn clamp(n: f64) -> f64 {
         n.clamp(0.0, 360.0)
     }
+    /// values already in `[0, 360]` are left alone, so `360.0` stays `360.0`
     fn wrap(n: f64) -> f64 {
-        if n < 0.0 {
-            360.0 + n
-        } else if n > 360.0 {
-            n - 360.0
-        } else {
+        if (0.0..=360.0).contains(&n) {
             n
+        } else {
+            n.rem_euclid(360.0)
         }
     }
     fn wrapped(n: f64) -> Self {
@@ -38,6 +37,41 @@ impl Hue {
     pub fn new(n: f64) -> Self {
         Self(Self::clamp(n))
     }
+    /// the shortest angular distance to `other`, from `0.0` to `180.0`
+    ///
+    /// ```
+    /// # use colorscheme::hue::Hue;
+    /// assert_eq!(Hue::new(10.0).distance(Hue::new(350.0)), 20.0);
+    /// ```
+    pub fn distance(self, other: Hue) -> f64 {
+        let d = (self.0 - other.0).abs() % 360.0;
+        if d > 180.0 { 360.0 - d } else { d }
+    }
+    /// whether `other` is within `epsilon` degrees of `self` around the color
+    /// wheel, for comparing hues that have picked up floating-point error
+    ///
+    /// ```
+    /// # use colorscheme::hue::Hue;
+    /// assert!(Hue::new(359.9999).approx_eq(Hue::new(0.0001), 0.001));
+    /// ```
+    pub fn approx_eq(self, other: Hue, epsilon: f64) -> bool {
+        self.distance(other) <= epsilon
+    }
+    /// the hue `t` of the way from `self` to `other`, going the shorter way
+    /// around the color wheel
+    ///
+    /// ```
+    /// # use colorscheme::hue::Hue;
+    /// let h = Hue::new(350.0).lerp(Hue::new(10.0), 0.5);
+    /// assert_eq!(f64::from(h), 0.0);
+    /// ```
+    pub fn lerp(self, other: Hue, t: f64) -> Self {
+        let mut delta = (other.0 - self.0).rem_euclid(360.0);
+        if delta > 180.0 {
+            delta -= 360.0;
+        }
+        Self((self.0 + delta * t).rem_euclid(360.0))
+    }
 }
 
 impl From<f64> for Hue {
@@ -68,6 +102,22 @@ impl std::ops::Sub<f64> for Hue {
     }
 }
 
+impl std::ops::Mul<f64> for Hue {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self {
+        Self::wrapped(self.0 * other)
+    }
+}
+
+impl std::ops::Neg for Hue {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::wrapped(360.0 - self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +133,45 @@ mod tests {
         let new = h + 120.0;
         assert_eq!(30.0, new.0);
     }
+    #[test]
+    fn test_lerp() {
+        assert_eq!(Hue::new(350.0).lerp(Hue::new(10.0), 0.5).0, 0.0);
+        assert_eq!(Hue::new(10.0).lerp(Hue::new(350.0), 0.25).0, 5.0);
+        assert_eq!(Hue::new(100.0).lerp(Hue::new(200.0), 0.5).0, 150.0);
+        assert_eq!(Hue::new(100.0).lerp(Hue::new(200.0), 0.0).0, 100.0);
+        assert_eq!(Hue::new(100.0).lerp(Hue::new(200.0), 1.0).0, 200.0);
+    }
+    #[test]
+    fn test_distance() {
+        assert_eq!(Hue::new(30.0).distance(Hue::new(100.0)), 70.0);
+        assert_eq!(Hue::new(100.0).distance(Hue::new(30.0)), 70.0);
+        assert_eq!(Hue::new(10.0).distance(Hue::new(350.0)), 20.0);
+        assert_eq!(Hue::new(90.0).distance(Hue::new(270.0)), 180.0);
+        assert_eq!(Hue::new(0.0).distance(Hue::new(360.0)), 0.0);
+    }
+    #[test]
+    fn test_approx_eq() {
+        assert!(Hue::new(359.9999).approx_eq(Hue::new(0.0001), 0.001));
+        assert!(Hue::new(0.0001).approx_eq(Hue::new(359.9999), 0.001));
+        assert!((Hue::new(0.1) + 0.2).approx_eq(Hue::new(0.3), 1e-9));
+        assert!(Hue::new(360.0).approx_eq(Hue::new(0.0), 0.0));
+        assert!(!Hue::new(10.0).approx_eq(Hue::new(20.0), 0.001));
+        assert!(!Hue::new(359.0).approx_eq(Hue::new(1.0), 1.0));
+    }
+    #[test]
+    fn test_mul() {
+        assert_eq!(Hue::new(90.0) * 2.0, Hue::new(180.0));
+        assert_eq!(Hue::new(300.0) * 3.0, Hue::new(180.0));
+        assert_eq!(Hue::new(90.0) * -1.0, Hue::new(270.0));
+    }
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Hue::new(90.0), Hue::new(270.0));
+        assert_eq!(-Hue::new(270.0), Hue::new(90.0));
+    }
+    #[test]
+    fn test_wrap_multiple_turns() {
+        assert_eq!((Hue::new(10.0) + 720.0).0, 10.0);
+        assert_eq!((Hue::new(10.0) - 740.0).0, 350.0);
+    }
 }
@@ -0,0 +1,294 @@
+//! # CIELAB/Oklab conversion and color difference
+//!
+//! [`colorsys`] only knows about RGB-derived spaces, so perceptual comparisons
+//! go through CIE XYZ (D65) into CIELAB, or into [Oklab](https://bottosson.github.io/posts/oklab/), here
+
+use colorsys::{Hsl, Rgb};
+
+/// deltaE above which two colors are no longer considered the same brand color
+pub const DEFAULT_DELTA_E_THRESHOLD: f64 = 2.0;
+
+/// D65 reference white
+const WHITE: (f64, f64, f64) = (95.047, 100.0, 108.883);
+
+/// a color in the CIELAB color space
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl From<&Hsl> for Lab {
+    fn from(color: &Hsl) -> Self {
+        Lab::from(&Rgb::from(color))
+    }
+}
+
+impl From<&Rgb> for Lab {
+    fn from(color: &Rgb) -> Self {
+        let r = linearize(color.red());
+        let g = linearize(color.green());
+        let b = linearize(color.blue());
+        let x = (r * 0.4124564 + g * 0.3575761 + b * 0.1804375) * 100.0;
+        let y = (r * 0.2126729 + g * 0.7151522 + b * 0.0721750) * 100.0;
+        let z = (r * 0.0193339 + g * 0.1191920 + b * 0.9503041) * 100.0;
+        let fx = lab_f(x / WHITE.0);
+        let fy = lab_f(y / WHITE.1);
+        let fz = lab_f(z / WHITE.2);
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+/// a color in the Oklab color space, in its cylindrical (lightness, chroma, hue) form
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Oklch {
+    /// `0.0` (black) to `1.0` (white)
+    pub l: f64,
+    pub c: f64,
+    /// degrees
+    pub h: f64,
+}
+
+impl From<&Hsl> for Oklch {
+    fn from(color: &Hsl) -> Self {
+        let rgb = Rgb::from(color);
+        let r = linearize(rgb.red());
+        let g = linearize(rgb.green());
+        let b = linearize(rgb.blue());
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+        Oklch {
+            l: lightness,
+            c: a.hypot(b),
+            h: hue_angle(b, a),
+        }
+    }
+}
+
+impl Oklch {
+    /// the sRGB color, or `None` if it's outside the sRGB gamut
+    pub fn to_rgb(&self) -> Option<Rgb> {
+        let (a, b) = (
+            self.c * self.h.to_radians().cos(),
+            self.c * self.h.to_radians().sin(),
+        );
+        let l = (self.l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (self.l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (self.l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+        let channels = [
+            4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        ];
+        // a little slack for rounding error at the edges of the gamut
+        if channels.iter().any(|c| !(-1e-6..=1.0 + 1e-6).contains(c)) {
+            return None;
+        }
+        let [r, g, b] = channels.map(|c| delinearize(c.clamp(0.0, 1.0)));
+        Some(Rgb::new(r, g, b, None))
+    }
+    /// the sRGB color, with chroma reduced as far as needed to bring it into the
+    /// sRGB gamut while keeping lightness and hue
+    pub fn to_rgb_clipped(&self) -> Rgb {
+        if let Some(rgb) = self.to_rgb() {
+            return rgb;
+        }
+        let (mut low, mut high) = (0.0, self.c);
+        for _ in 0..32 {
+            let c = (low + high) / 2.0;
+            if (Oklch { c, ..*self }).to_rgb().is_some() {
+                low = c;
+            } else {
+                high = c;
+            }
+        }
+        // safety: zero chroma is a gray, always in gamut for lightness in `[0, 1]`
+        Oklch {
+            l: self.l.clamp(0.0, 1.0),
+            c: low,
+            h: self.h,
+        }
+        .to_rgb()
+        .unwrap()
+    }
+}
+
+/// convert an sRGB channel (`0.0`-`255.0`) to linear light (`0.0`-`1.0`)
+pub(crate) fn linearize(channel: f64) -> f64 {
+    let c = channel / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// the inverse of [`linearize`]
+fn delinearize(c: f64) -> f64 {
+    let c = if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    c * 255.0
+}
+
+fn lab_f(t: f64) -> f64 {
+    let delta: f64 = 6.0 / 29.0;
+    if t > delta.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * delta * delta) + 4.0 / 29.0
+    }
+}
+
+/// perceived difference between two colors using
+/// [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+///
+/// `0.0` means identical; differences around `1.0` are barely noticeable
+pub fn delta_e(first: &Hsl, second: &Hsl) -> f64 {
+    ciede2000(Lab::from(first), Lab::from(second))
+}
+
+fn ciede2000(x: Lab, y: Lab) -> f64 {
+    let c1 = x.a.hypot(x.b);
+    let c2 = y.a.hypot(y.b);
+    let c_bar = (c1 + c2) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + 25f64.powi(7))).sqrt());
+    let a1 = x.a * (1.0 + g);
+    let a2 = y.a * (1.0 + g);
+    let c1 = a1.hypot(x.b);
+    let c2 = a2.hypot(y.b);
+    let h1 = hue_angle(x.b, a1);
+    let h2 = hue_angle(y.b, a2);
+
+    let delta_l = y.l - x.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_bar = (x.l + y.l) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + 25f64.powi(7))).sqrt();
+    let s_l = 1.0 + (0.015 * (l_bar - 50.0).powi(2)) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l_term = delta_l / s_l;
+    let c_term = delta_c / s_c;
+    let h_term = delta_h / s_h;
+    (l_term.powi(2) + c_term.powi(2) + h_term.powi(2) + r_t * c_term * h_term).sqrt()
+}
+
+fn hue_angle(b: f64, a: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        let h = b.atan2(a).to_degrees();
+        if h < 0.0 { h + 360.0 } else { h }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_lab_white() {
+        let white = Lab::from(&Rgb::new(255.0, 255.0, 255.0, None));
+        assert!((white.l - 100.0).abs() < 0.01);
+        assert!(white.a.abs() < 0.01);
+        assert!(white.b.abs() < 0.01);
+    }
+    #[test]
+    fn test_oklch_red() {
+        let red = Oklch::from(&Hsl::new(0.0, 100.0, 50.0, None));
+        assert!((red.l - 0.62796).abs() < 0.0001);
+        assert!((red.c - 0.25768).abs() < 0.0001);
+        assert!((red.h - 29.2339).abs() < 0.001);
+    }
+    #[test]
+    fn test_oklch_round_trip() {
+        for rgb in [
+            (255.0, 0.0, 0.0),
+            (51.0, 153.0, 57.0),
+            (0.0, 0.0, 0.0),
+            (255.0, 255.0, 255.0),
+        ] {
+            let color = Rgb::new(rgb.0, rgb.1, rgb.2, None);
+            let back = Oklch::from(&Hsl::from(&color)).to_rgb().unwrap();
+            assert!((back.red() - color.red()).abs() < 1e-3);
+            assert!((back.green() - color.green()).abs() < 1e-3);
+            assert!((back.blue() - color.blue()).abs() < 1e-3);
+        }
+    }
+    #[test]
+    fn test_oklch_clipped() {
+        let vivid = Oklch {
+            l: 0.9,
+            c: 0.3,
+            h: 29.0,
+        };
+        assert!(vivid.to_rgb().is_none());
+        let clipped = Oklch::from(&Hsl::from(vivid.to_rgb_clipped()));
+        assert!((clipped.l - 0.9).abs() < 1e-3);
+        assert!(clipped.c < 0.3);
+    }
+    #[test]
+    fn test_ciede2000_reference() {
+        // pair 1 of Sharma, Wu & Dalal's CIEDE2000 test data
+        let x = Lab {
+            l: 50.0,
+            a: 2.6772,
+            b: -79.7751,
+        };
+        let y = Lab {
+            l: 50.0,
+            a: 0.0,
+            b: -82.7485,
+        };
+        assert!((ciede2000(x, y) - 2.0425).abs() < 0.0001);
+    }
+    #[test]
+    fn test_delta_e_identical() {
+        let c = Hsl::new(210.0, 50.0, 40.0, None);
+        assert!(delta_e(&c, &c.clone()) < 1e-9);
+    }
+    #[test]
+    fn test_delta_e_different() {
+        let red = Hsl::new(0.0, 100.0, 50.0, None);
+        let blue = Hsl::new(240.0, 100.0, 50.0, None);
+        assert!(delta_e(&red, &blue) > DEFAULT_DELTA_E_THRESHOLD);
+    }
+}
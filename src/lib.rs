@@ -1,5 +1,11 @@
+#[cfg(feature = "cli")]
 pub mod cli;
+mod color;
+pub mod contrast;
 pub mod hue;
+pub mod lab;
+pub mod parse;
 pub mod scheme;
 
-pub use scheme::ColorScheme;
+pub use hue::Hue;
+pub use scheme::{ColorScheme, ColorSchemeBuilder, Scheme};
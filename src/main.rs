@@ -1,12 +1,102 @@
 use clap::Parser;
-use colorscheme::{cli::Args, scheme::ColorScheme};
+use colorscheme::{
+    ColorScheme,
+    cli::{self, Args, Command},
+    lab,
+};
 fn main() -> Result<(), String> {
-    let args = Args::parse();
-    if let Some(primary) = args.primary() {
-        let scheme = ColorScheme::from_schemes(primary, args.schemes());
-        println!("{}", scheme.as_css(args.selector.as_deref()));
-        Ok(())
-    } else {
-        Err(String::from("invalid primary color"))
+    let args = Args::parse().with_config()?;
+    args.validate()?;
+    match &args.command {
+        Some(Command::Convert(convert)) => {
+            println!("{}", convert.convert());
+            return Ok(());
+        }
+        Some(Command::Explain(explain)) => {
+            println!("{}", explain.explain());
+            return Ok(());
+        }
+        None => {}
+    }
+    if let Some(ramp) = args.ramp() {
+        ramp.validate()?;
+        return write_output(&args, &args.render(&ramp));
+    }
+    if args.reads_stdin() {
+        return batch(&args);
+    }
+    let primary = args.primary().map_err(|e| e.to_string())?;
+    let scheme = args.color_scheme(primary.clone());
+    scheme.validate()?;
+    diagnose(&args, &primary, &scheme, "");
+    write_output(&args, &args.render(&scheme))
+}
+
+/// print the notices, warnings, and statistics the flags ask for to stderr,
+/// each message starting with `label`
+fn diagnose(args: &Args, primary: &colorsys::Hsl, scheme: &ColorScheme, label: &str) {
+    if let Some(brand) = &args.brand {
+        let delta = lab::delta_e(primary, brand);
+        eprintln!("{}deltaE from brand color: {:.2}", label, delta);
+        if delta > args.brand_threshold() {
+            eprintln!(
+                "{}warning: primary color exceeds brand threshold of {:.2}",
+                label,
+                args.brand_threshold()
+            );
+        }
+    }
+    if let Some(notice) = args.verbose.then(|| args.precision_loss(primary)).flatten() {
+        eprintln!("{}notice: {}", label, notice);
+    }
+    for warning in scheme.warnings() {
+        eprintln!("{}warning: {}", label, warning);
+    }
+    if let Some(standard) = args.contrast_standard() {
+        for failure in scheme.contrast_failures(standard) {
+            eprintln!("{}warning: {}", label, failure);
+        }
+    }
+    if args.stats {
+        eprintln!("{}harmony score: {:.2}", label, scheme.harmony_score());
+    }
+}
+
+/// a scheme for every color on stdin, each under a `.scheme-<line number>` selector
+///
+/// lines that aren't colors, or whose schemes don't validate, are reported
+/// without stopping the rest; diagnostics are labeled with each line's selector
+fn batch(args: &Args) -> Result<(), String> {
+    args.validate_batch()?;
+    let primaries = cli::read_primaries(std::io::stdin().lock())
+        .map_err(|e| format!("couldn't read stdin: {}", e))?;
+    let mut outputs = Vec::new();
+    for (line, primary) in primaries {
+        match primary {
+            Ok(primary) => {
+                let scheme = args.color_scheme(primary.clone());
+                if let Err(e) = scheme.validate() {
+                    eprintln!("line {}: {}", line, e);
+                    continue;
+                }
+                let selector = format!(".scheme-{}", line);
+                diagnose(args, &primary, &scheme, &format!("{}: ", selector));
+                outputs.push(args.render_under(&scheme, Some(&selector)));
+            }
+            Err(e) => eprintln!("line {}: {}", line, e),
+        }
+    }
+    write_output(args, &outputs.join("\n\n"))
+}
+
+/// print `output`, or write it to `--output` with a trailing newline
+fn write_output(args: &Args, output: &str) -> Result<(), String> {
+    match &args.output {
+        Some(path) => std::fs::write(path, format!("{}\n", output))
+            .map_err(|e| format!("couldn't write to {}: {}", path.display(), e)),
+        None => {
+            println!("{}", output);
+            Ok(())
+        }
     }
 }
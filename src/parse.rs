@@ -0,0 +1,719 @@
+//! # color parsing
+//!
+//! the color notations accepted for the primary and anywhere else a color is
+//! given: hex, CSS color names, `rgb()`/`hsl()`/`hsv()`, and bare channels
+use colorsys::{Hsl, Rgb};
+#[cfg(feature = "named-colors")]
+use css_named_colors::NamedColor;
+
+/// why a color couldn't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// nothing but whitespace
+    EmptyInput,
+    /// `transparent` is a CSS color, but has no hue to build a scheme from
+    TransparentNotSupported,
+    /// a `#` followed by something other than 3, 4, 6, or 8 hex digits, or a
+    /// bare hex string of the wrong length
+    InvalidHex(String),
+    /// space- or comma-separated channels that aren't `r g b` or `r g b a`
+    InvalidChannels(String),
+    /// `rgb()`, `hsl()`, or `hsv()` notation with bad arguments, or another CSS function
+    InvalidFunction(String),
+    /// neither a CSS color name nor hex digits, with the closest color name if
+    /// there's one within a couple of typos
+    UnknownColorName(String, Option<&'static str>),
+}
+
+impl std::fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorParseError::EmptyInput => write!(f, "no color given"),
+            ColorParseError::TransparentNotSupported => {
+                write!(f, "`transparent` has no hue to build a scheme from")
+            }
+            ColorParseError::InvalidHex(s) => write!(
+                f,
+                "`{}` isn't a valid hex color; expected 3, 4, 6, or 8 hex digits",
+                s
+            ),
+            ColorParseError::InvalidChannels(s) => write!(
+                f,
+                "`{}` isn't a valid list of channels; expected `r g b` or `r g b a` with channels from 0 to 255 and alpha between 0 and 1",
+                s
+            ),
+            ColorParseError::InvalidFunction(s) => {
+                write!(
+                    f,
+                    "`{}` isn't a valid `rgb()`, `hsl()`, or `hsv()` color",
+                    s
+                )
+            }
+            ColorParseError::UnknownColorName(s, suggestion) => {
+                write!(f, "`{}` isn't a CSS color name or hex color", s)?;
+                match suggestion {
+                    Some(name) => write!(f, "; did you mean `{}`?", name),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// parse a hex string, CSS color name, `rgb()`/`hsl()`/`hsv()` notation, or bare
+/// `r g b` channels
+///
+/// surrounding whitespace is ignored; CSS color names need the `named-colors`
+/// feature
+pub fn parse_color(primary: &str) -> Result<Hsl, ColorParseError> {
+    let primary = primary.trim();
+    let invalid = |error: fn(String) -> ColorParseError| error(primary.to_string());
+    if primary.is_empty() {
+        Err(ColorParseError::EmptyInput)
+    } else if let Some('#') = primary.chars().next() {
+        // hex string
+        parse_hex(primary)
+            .map(|c| c.into())
+            .ok_or_else(|| invalid(ColorParseError::InvalidHex))
+    } else if primary.contains('(') {
+        // `rgb(...)`, `hsl(...)`, or `hsv(...)`
+        parse_function(primary).ok_or_else(|| invalid(ColorParseError::InvalidFunction))
+    } else if let Some(color) = named_color(primary) {
+        color
+    } else if primary.contains([' ', ',']) {
+        // bare channel values?
+        parse_channels(primary)
+            .map(|c| c.into())
+            .ok_or_else(|| invalid(ColorParseError::InvalidChannels))
+    } else if primary.chars().all(|c| c.is_ascii_hexdigit()) {
+        // hex without the hash?
+        parse_hex(primary)
+            .map(|c| c.into())
+            .ok_or_else(|| invalid(ColorParseError::InvalidHex))
+    } else {
+        Err(ColorParseError::UnknownColorName(
+            primary.to_string(),
+            suggest_color_name(primary),
+        ))
+    }
+}
+/// parse CSS `rgb()`/`rgba()` or `hsl()`/`hsla()` notation, or the
+/// `hsv()`/`hsb()` notation of many design tools, with commas or spaces
+/// between the arguments and an optional `/ alpha`
+///
+/// the percent signs on saturation, lightness, and value are optional
+fn parse_function(function: &str) -> Option<Hsl> {
+    let function = function.to_ascii_lowercase();
+    let (name, arguments) = function.strip_suffix(')')?.split_once('(')?;
+    let arguments = arguments.replace('/', " ");
+    match name.trim_end() {
+        "rgb" | "rgba" => parse_channels(&arguments).map(|c| c.into()),
+        "hsl" | "hsla" => {
+            let components: Vec<&str> = arguments
+                .split([' ', ','])
+                .filter(|part| !part.is_empty())
+                .map(|part| part.strip_suffix('%').unwrap_or(part))
+                .collect();
+            parse_hsl(&components.join(",")).ok()
+        }
+        "hsv" | "hsva" | "hsb" | "hsba" => parse_hsv(&arguments),
+        _ => None,
+    }
+}
+/// parse the arguments of `hsv()`, hue in degrees and saturation and value
+/// from 0 to 100
+fn parse_hsv(arguments: &str) -> Option<Hsl> {
+    let components = arguments
+        .split([' ', ','])
+        .filter(|part| !part.is_empty())
+        .map(|part| part.strip_suffix('%').unwrap_or(part).parse::<f64>().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    let (h, sat, v, a) = match components[..] {
+        [h, sat, v] => (h, sat, v, None),
+        [h, sat, v, a] => (h, sat, v, Some(a)),
+        _ => return None,
+    };
+    let in_range = |x: f64, max: f64| (0.0..=max).contains(&x);
+    if !h.is_finite() || !in_range(sat, 100.0) || !in_range(v, 100.0) {
+        return None;
+    }
+    if a.is_some_and(|a| !in_range(a, 1.0)) {
+        return None;
+    }
+    Some(hsv_to_hsl(h.rem_euclid(360.0), sat, v, a))
+}
+/// parse space- and/or comma-separated `r g b` or `r g b a` channel values,
+/// as copied out of many design tools
+///
+/// red, green, and blue must be integers from 0 to 255 and alpha between 0 and 1
+fn parse_channels(channels: &str) -> Option<Rgb> {
+    let parts: Vec<&str> = channels
+        .split([' ', ','])
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (rgb, alpha) = match parts[..] {
+        [r, g, b] => ([r, g, b], None),
+        [r, g, b, a] => ([r, g, b], Some(a.parse::<f64>().ok()?)),
+        _ => return None,
+    };
+    if alpha.is_some_and(|a| !(0.0..=1.0).contains(&a)) {
+        return None;
+    }
+    let [r, g, b] = rgb.map(|part| part.parse::<u8>().ok().map(f64::from));
+    Some(Rgb::new(r?, g?, b?, alpha))
+}
+/// parse a 3, 4, 6, or 8 digit hex string with or without a leading `#`
+///
+/// 4 and 8 digit strings carry an alpha channel
+fn parse_hex(hex: &str) -> Option<Rgb> {
+    // `to_digit` and `from_str_radix` accept either case, but normalizing
+    // keeps that from being something to rely on
+    let digits = hex.strip_prefix('#').unwrap_or(hex).to_ascii_lowercase();
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channels: Vec<f64> = match digits.len() {
+        // shorthand, each digit is doubled
+        3 | 4 => digits
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| f64::from(d * 17)))
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..digits.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(&digits[i..i + 2], 16)
+                    .ok()
+                    .map(f64::from)
+            })
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    let alpha = channels.get(3).map(|a| a / 255.0);
+    Some(Rgb::new(channels[0], channels[1], channels[2], alpha))
+}
+
+/// a CSS color name, or `None` if `name` isn't one
+#[cfg(feature = "named-colors")]
+fn named_color(name: &str) -> Option<Result<Hsl, ColorParseError>> {
+    if name == NamedColor::TRANSPARENT.name() {
+        // 'transparent' is a valid CSS color name but not useful to us
+        return Some(Err(ColorParseError::TransparentNotSupported));
+    }
+    // safety: we know `nc` is not `TRANSPARENT`
+    NamedColor::from_name(name).map(|nc| {
+        let (r, g, b) = nc.rgb().unwrap();
+        Ok(Rgb::new(r.into(), g.into(), b.into(), None).into())
+    })
+}
+
+/// without the `named-colors` feature, no names are known
+#[cfg(not(feature = "named-colors"))]
+fn named_color(_name: &str) -> Option<Result<Hsl, ColorParseError>> {
+    None
+}
+
+/// every CSS color name but `transparent`
+#[cfg(feature = "named-colors")]
+pub(crate) const COLOR_NAMES: &[&str] = &[
+    "aliceblue",
+    "antiquewhite",
+    "aqua",
+    "aquamarine",
+    "azure",
+    "beige",
+    "bisque",
+    "black",
+    "blanchedalmond",
+    "blue",
+    "blueviolet",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornflowerblue",
+    "cornsilk",
+    "crimson",
+    "cyan",
+    "darkblue",
+    "darkcyan",
+    "darkgoldenrod",
+    "darkgray",
+    "darkgreen",
+    "darkgrey",
+    "darkkhaki",
+    "darkmagenta",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkred",
+    "darksalmon",
+    "darkseagreen",
+    "darkslateblue",
+    "darkslategray",
+    "darkslategrey",
+    "darkturquoise",
+    "darkviolet",
+    "deeppink",
+    "deepskyblue",
+    "dimgray",
+    "dimgrey",
+    "dodgerblue",
+    "firebrick",
+    "floralwhite",
+    "forestgreen",
+    "fuchsia",
+    "gainsboro",
+    "ghostwhite",
+    "gold",
+    "goldenrod",
+    "gray",
+    "green",
+    "greenyellow",
+    "grey",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "indigo",
+    "ivory",
+    "khaki",
+    "lavender",
+    "lavenderblush",
+    "lawngreen",
+    "lemonchiffon",
+    "lightblue",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrodyellow",
+    "lightgray",
+    "lightgreen",
+    "lightgrey",
+    "lightpink",
+    "lightsalmon",
+    "lightseagreen",
+    "lightskyblue",
+    "lightslategray",
+    "lightslategrey",
+    "lightsteelblue",
+    "lightyellow",
+    "lime",
+    "limegreen",
+    "linen",
+    "magenta",
+    "maroon",
+    "mediumaquamarine",
+    "mediumblue",
+    "mediumorchid",
+    "mediumpurple",
+    "mediumseagreen",
+    "mediumslateblue",
+    "mediumspringgreen",
+    "mediumturquoise",
+    "mediumvioletred",
+    "midnightblue",
+    "mintcream",
+    "mistyrose",
+    "moccasin",
+    "navajowhite",
+    "navy",
+    "oldlace",
+    "olive",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegoldenrod",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "papayawhip",
+    "peachpuff",
+    "peru",
+    "pink",
+    "plum",
+    "powderblue",
+    "purple",
+    "rebeccapurple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "saddlebrown",
+    "salmon",
+    "sandybrown",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "silver",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "slategrey",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "teal",
+    "thistle",
+    "tomato",
+    "turquoise",
+    "violet",
+    "wheat",
+    "white",
+    "whitesmoke",
+    "yellow",
+    "yellowgreen",
+];
+
+/// the CSS color name closest to `color`, by Euclidean distance in RGB
+///
+/// alpha is ignored; ties go to the first name alphabetically, e.g. `aqua` rather
+/// than `cyan`
+#[cfg(feature = "named-colors")]
+pub fn nearest_named_color(color: &Hsl) -> &'static str {
+    let rgb = Rgb::from(color);
+    let distance = |name: &str| {
+        // safety: every name in `COLOR_NAMES` is a color with channels
+        let (r, g, b) = NamedColor::from_name(name).and_then(|nc| nc.rgb()).unwrap();
+        (rgb.red() - f64::from(r)).powi(2)
+            + (rgb.green() - f64::from(g)).powi(2)
+            + (rgb.blue() - f64::from(b)).powi(2)
+    };
+    COLOR_NAMES
+        .iter()
+        .copied()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        // safety: `COLOR_NAMES` isn't empty
+        .unwrap()
+}
+
+/// the color name closest to `name` by edit distance, if it's at most 2 edits away
+///
+/// ties go to the first name alphabetically
+#[cfg(feature = "named-colors")]
+fn suggest_color_name(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    COLOR_NAMES
+        .iter()
+        .map(|candidate| (edit_distance(&name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(not(feature = "named-colors"))]
+fn suggest_color_name(_name: &str) -> Option<&'static str> {
+    None
+}
+
+/// [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
+/// between `a` and `b`
+#[cfg(feature = "named-colors")]
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// parse a comma-separated `h,s,l` or `h,s,l,a` color
+///
+/// out-of-range saturation, lightness, and alpha are errors rather than being
+/// clamped, while hue wraps around the color wheel
+pub(crate) fn parse_hsl(s: &str) -> Result<Hsl, String> {
+    let components = s
+        .split(',')
+        .map(|c| {
+            c.trim()
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number `{}`", c.trim()))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    let (h, sat, l, a) = match components[..] {
+        [h, sat, l] => (h, sat, l, None),
+        [h, sat, l, a] => (h, sat, l, Some(a)),
+        _ => return Err(format!("expected H,S,L or H,S,L,A, got `{}`", s)),
+    };
+    if !(0.0..=100.0).contains(&sat) {
+        return Err(format!("saturation must be between 0 and 100, got {}", sat));
+    }
+    if !(0.0..=100.0).contains(&l) {
+        return Err(format!("lightness must be between 0 and 100, got {}", l));
+    }
+    if let Some(a) = a.filter(|a| !(0.0..=1.0).contains(a)) {
+        return Err(format!("alpha must be between 0 and 1, got {}", a));
+    }
+    if !h.is_finite() {
+        return Err(format!("invalid hue {}", h));
+    }
+    Ok(Hsl::new(h.rem_euclid(360.0), sat, l, a))
+}
+
+/// convert HSV (saturation and value from 0 to 100) to HSL
+///
+/// black and grays, which have no saturation in one space or the other, come out
+/// with a saturation of 0
+fn hsv_to_hsl(h: f64, sat: f64, v: f64, a: Option<f64>) -> Hsl {
+    let (sat, v) = (sat / 100.0, v / 100.0);
+    let l = v * (1.0 - sat / 2.0);
+    let sat = if l == 0.0 || l == 1.0 {
+        0.0
+    } else {
+        (v - l) / l.min(1.0 - l)
+    };
+    Hsl::new(h, sat * 100.0, l * 100.0, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheme::{Format, format_color};
+    use colorsys::ColorAlpha;
+    #[test]
+    fn test_parse_hex_alpha() {
+        let translucent = parse_color("#ff000080").unwrap();
+        assert_eq!(translucent.hue(), 0.0);
+        assert_eq!(translucent.alpha(), 128.0 / 255.0);
+        let shorthand = parse_color("f008").unwrap();
+        assert_eq!(shorthand.alpha(), 136.0 / 255.0);
+    }
+    #[test]
+    fn test_parse_hex_opaque() {
+        let opaque = parse_color("#ff0000").unwrap();
+        assert_eq!(opaque.alpha(), 1.0);
+        assert_eq!(
+            parse_color("#ff00000"),
+            Err(ColorParseError::InvalidHex(String::from("#ff00000")))
+        );
+    }
+    #[test]
+    fn test_parse_hex_case() {
+        let red = parse_color("#ff0000").unwrap();
+        for hex in ["#FF0000", "FF0000", "Ff0000", "#fF0000", "#F00", "F00"] {
+            assert_eq!(parse_color(hex), Ok(red.clone()), "{}", hex);
+        }
+    }
+    #[test]
+    fn test_parse_hsl() {
+        let hsl = parse_hsl("210, 50, 40").unwrap();
+        assert_eq!(
+            (hsl.hue(), hsl.saturation(), hsl.lightness()),
+            (210.0, 50.0, 40.0)
+        );
+        let translucent = parse_hsl("0,100,50,0.5").unwrap();
+        assert_eq!(translucent.alpha(), 0.5);
+        assert_eq!(parse_hsl("400,100,50").unwrap().hue(), 40.0);
+    }
+    #[test]
+    fn test_parse_function_out_of_range() {
+        for input in [
+            "hsl(0 100% 150%)",
+            "hsl(0 -10% 50%)",
+            "hsl(0 100% NaN%)",
+            "hsl(NaN 100% 50%)",
+            "hsv(0 NaN 50)",
+        ] {
+            assert_eq!(
+                parse_color(input),
+                Err(ColorParseError::InvalidFunction(input.to_string())),
+            );
+        }
+    }
+    #[test]
+    fn test_parse_hsl_out_of_range() {
+        assert_eq!(
+            parse_hsl("0,150,50"),
+            Err(String::from(
+                "saturation must be between 0 and 100, got 150"
+            ))
+        );
+        assert_eq!(
+            parse_hsl("0,50,-1"),
+            Err(String::from("lightness must be between 0 and 100, got -1"))
+        );
+        assert_eq!(
+            parse_hsl("0,50,50,2"),
+            Err(String::from("alpha must be between 0 and 1, got 2"))
+        );
+        assert!(parse_hsl("0,50").is_err());
+        assert!(parse_hsl("0,fifty,50").is_err());
+    }
+    #[test]
+    fn test_parse_channels() {
+        let red = parse_color("255 0 0").unwrap();
+        assert_eq!(Rgb::from(&red).to_hex_string(), "#ff0000");
+        let translucent = parse_color("255, 0, 0, 0.5").unwrap();
+        assert_eq!(Rgb::from(&translucent).to_hex_string(), "#ff0000");
+        assert_eq!(translucent.alpha(), 0.5);
+        assert_eq!(
+            parse_color("256 0 0"),
+            Err(ColorParseError::InvalidChannels(String::from("256 0 0")))
+        );
+        assert_eq!(
+            parse_color("255 0"),
+            Err(ColorParseError::InvalidChannels(String::from("255 0")))
+        );
+        assert_eq!(
+            parse_color("255 0 0 2"),
+            Err(ColorParseError::InvalidChannels(String::from("255 0 0 2")))
+        );
+    }
+    #[test]
+    fn test_parse_primary() {
+        let white = Ok(Hsl::new(0.0, 0.0, 100.0, None));
+        assert_eq!(parse_color("#fff"), white);
+        assert_eq!(parse_color("#ffffff"), white);
+        assert_eq!(parse_color("#ffffffff"), white);
+        assert_eq!(parse_color("fff"), white);
+        assert_eq!(parse_color("ffffff"), white);
+        assert_eq!(parse_color("  #ffffff\n"), white);
+        let hex_error = |s: &str| Err(ColorParseError::InvalidHex(String::from(s)));
+        assert_eq!(parse_color(""), Err(ColorParseError::EmptyInput));
+        assert_eq!(parse_color("   "), Err(ColorParseError::EmptyInput));
+        assert_eq!(parse_color("#"), hex_error("#"));
+        assert_eq!(
+            parse_color("not-a-color"),
+            Err(ColorParseError::UnknownColorName(
+                String::from("not-a-color"),
+                None
+            ))
+        );
+        assert_eq!(parse_color("#ggg"), hex_error("#ggg"));
+        assert_eq!(parse_color("#fffff"), hex_error("#fffff"));
+        assert_eq!(parse_color("fffff"), hex_error("fffff"));
+    }
+    #[test]
+    #[cfg(feature = "named-colors")]
+    fn test_parse_named_color() {
+        assert_eq!(parse_color("red"), Ok(Hsl::new(0.0, 100.0, 50.0, None)));
+        assert_eq!(parse_color("\tred "), Ok(Hsl::new(0.0, 100.0, 50.0, None)));
+        assert_eq!(
+            parse_color("transparent"),
+            Err(ColorParseError::TransparentNotSupported)
+        );
+        assert_eq!(
+            parse_color(" transparent "),
+            Err(ColorParseError::TransparentNotSupported)
+        );
+    }
+    #[test]
+    #[cfg(feature = "named-colors")]
+    fn test_suggest_color_name() {
+        assert_eq!(
+            parse_color("skyblu"),
+            Err(ColorParseError::UnknownColorName(
+                String::from("skyblu"),
+                Some("skyblue")
+            ))
+        );
+        let error = parse_color("skyblu").unwrap_err().to_string();
+        assert!(error.contains("did you mean `skyblue`?"));
+        assert_eq!(suggest_color_name("Tomatoe"), Some("tomato"));
+        assert_eq!(suggest_color_name("chartreuses"), Some("chartreuse"));
+        assert_eq!(suggest_color_name("zzzzzz"), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert!(
+            COLOR_NAMES
+                .iter()
+                .all(|name| NamedColor::from_name(name).is_some())
+        );
+    }
+    #[test]
+    #[cfg(feature = "named-colors")]
+    fn test_nearest_named_color() {
+        let cyan = Hsl::from(Rgb::new(0.0, 255.0, 255.0, None));
+        assert!(["aqua", "cyan"].contains(&nearest_named_color(&cyan)));
+        let almost_tomato = Hsl::from(Rgb::new(250.0, 100.0, 70.0, None));
+        assert_eq!(nearest_named_color(&almost_tomato), "tomato");
+        assert_eq!(
+            nearest_named_color(&Hsl::new(0.0, 0.0, 0.0, Some(0.5))),
+            "black"
+        );
+    }
+    #[test]
+    fn test_parse_hsv() {
+        let red = Hsl::new(0.0, 100.0, 50.0, None);
+        assert_eq!(parse_color("hsv(0, 100%, 100%)"), Ok(red.clone()));
+        assert_eq!(parse_color("hsb(360 100 100)"), Ok(red));
+        let black = parse_color("hsv(120, 100%, 0%)").unwrap();
+        assert_eq!((black.saturation(), black.lightness()), (0.0, 0.0));
+        let gray = parse_color("hsv(120, 0%, 50%)").unwrap();
+        assert_eq!((gray.saturation(), gray.lightness()), (0.0, 50.0));
+        let white = parse_color("hsv(0, 0%, 100%)").unwrap();
+        assert_eq!((white.saturation(), white.lightness()), (0.0, 100.0));
+        let translucent = parse_color("hsva(210, 50%, 40%, 0.5)").unwrap();
+        assert_eq!(translucent.alpha(), 0.5);
+        assert!(matches!(
+            parse_color("hsv(0, 100%, 150%)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+    }
+    #[test]
+    fn test_parse_function() {
+        let hsl = Hsl::new(210.0, 50.0, 40.0, None);
+        assert_eq!(parse_color("hsl(210, 50%, 40%)"), Ok(hsl.clone()));
+        assert_eq!(parse_color("hsl(210, 50, 40)"), Ok(hsl.clone()));
+        assert_eq!(parse_color("HSL(210 50% 40%)"), Ok(hsl));
+        let translucent = parse_color("hsla(210, 50%, 40%, 0.5)").unwrap();
+        assert_eq!(translucent.alpha(), 0.5);
+        let rgb = Hsl::from(Rgb::new(30.0, 60.0, 90.0, None));
+        assert_eq!(parse_color("rgb(30, 60, 90)"), Ok(rgb.clone()));
+        assert_eq!(parse_color("rgb(30 60 90)"), Ok(rgb));
+        let translucent = parse_color("rgba(30, 60, 90, 0.25)").unwrap();
+        assert_eq!(translucent.alpha(), 0.25);
+        assert_eq!(parse_color("rgb(30 60 90 / 0.25)"), Ok(translucent));
+        assert!(matches!(
+            parse_color("hsl(210, 150%, 40%)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+        assert!(matches!(
+            parse_color("hsl(210, x, 40%)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+        assert!(matches!(
+            parse_color("rgb(300, 60, 90)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+        assert!(matches!(
+            parse_color("rgb(30, 60)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+        assert!(matches!(
+            parse_color("rgb(30, 60, 90"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+        assert!(matches!(
+            parse_color("lab(50 20 20)"),
+            Err(ColorParseError::InvalidFunction(_))
+        ));
+    }
+    #[test]
+    fn test_parse_shorthand_hex() {
+        let expanded = |hex| parse_color(hex).ok().map(|c| format_color(&c, Format::Hex));
+        assert_eq!(expanded("#abc"), Some(String::from("#aabbcc")));
+        assert_eq!(expanded("#f00"), Some(String::from("#ff0000")));
+        assert_eq!(expanded("abc"), Some(String::from("#aabbcc")));
+        assert_eq!(expanded("abcg"), None);
+        assert_eq!(expanded("#abcg"), None);
+    }
+    #[test]
+    fn test_color_parse_error_display() {
+        assert_eq!(
+            ColorParseError::UnknownColorName(String::from("bleu"), None).to_string(),
+            "`bleu` isn't a CSS color name or hex color"
+        );
+        assert_eq!(
+            ColorParseError::UnknownColorName(String::from("bleu"), Some("blue")).to_string(),
+            "`bleu` isn't a CSS color name or hex color; did you mean `blue`?"
+        );
+    }
+}
@@ -1,16 +1,24 @@
 //! # color scheme generation
+use crate::color::Color;
+use crate::contrast::{ContrastStandard, readable_on, relative_luminance, solve_contrast};
 use crate::hue::Hue;
-use colorsys::Hsl;
+use crate::lab::{Oklch, delta_e};
+#[cfg(feature = "named-colors")]
+use crate::parse::nearest_named_color;
+use crate::parse::parse_color;
+use colorsys::{ColorAlpha, Hsl};
 use std::fmt::Write;
 
 /// color scheme variants    
 ///
 /// it may be worth referring to [a
 /// diagram of HSL](https://en.wikipedia.org/wiki/HSL_and_HSV#/media/File:HSL_color_solid_cylinder_saturation_gray.png)    
-/// schemes primarily affect hue while preserving saturation and lightness except where noted
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+/// schemes primarily affect hue while preserving saturation and lightness except where noted    
+/// NB: not [`Copy`], since [`Scheme::Custom`] carries its angles
+#[derive(Debug, PartialEq, Clone)]
 pub enum Scheme {
-    /// lighter and darker variants of the same hue    
+    /// lighter and darker variants of the same hue, at 1.5 and 0.5 times the
+    /// primary's lightness    
     /// variable names: `--lighter`, `--darker`
     Column,
     /// the complementary color (180 degrees on the color wheel)    
@@ -26,75 +34,1120 @@ pub enum Scheme {
     /// clockwise, 180 degrees clockwise, 90 degrees counterclockwise)    
     /// variable names: `--upper-right`, `--lower-right`, `--lower-left`
     Tetrad,
-    /// a dark and saturated variant suitable for use as a font color
+    /// a rectangle with the primary color as the upper-left corner (60, 180, and
+    /// 240 degrees clockwise): two complementary pairs 60 degrees apart    
+    /// variable names: `--rect-upper-right`, `--rect-lower-right`, `--rect-lower-left`
+    Rectangle,
+    /// the two neighbors of the primary (30 degrees clockwise and counterclockwise)    
+    /// variable names: `--analogous-clockwise`, `--analogous-counterclockwise`
+    Analogous,
+    /// the two neighbors of the complementary color (150 and 210 degrees clockwise)    
+    /// variable names: `--split-complementary-clockwise`, `--split-complementary-counterclockwise`
+    SplitComplementary,
+    /// the complementary color and its two neighbors (180, 150, and 210 degrees clockwise)    
+    /// variable names: `--complementary`, `--compound-clockwise`, `--compound-counterclockwise`
+    Compound,
+    /// a dark and saturated variant suitable for use as a font color, contrasting
+    /// with [`Scheme::Background`] by at least 4.5:1
     /// variable names: `--text-primary`
     Text,
     /// a light and desaturated variant for use as a background color
     /// variable names: `--background-primary`
     Background,
+    /// light, mid, and dark near-grays tinted with the primary hue    
+    /// variable names: `--neutral-light`, `--neutral`, `--neutral-dark`
+    Neutrals,
+    /// a soft variant at 40% saturation and 85% lightness    
+    /// variable names: `--pastel-primary`
+    Pastel,
+    /// two lighter tints and two darker shades, 15 and 30 points of lightness
+    /// either side of the primary    
+    /// variable names: `--tint-1`, `--tint-2`, `--shade-1`, `--shade-2`
+    Ramp,
+    /// the primary fully desaturated, keeping its lightness    
+    /// variable names: `--grayscale`
+    Grayscale,
+    /// the primary at 90%, 50%, and 10% opacity, for overlays and scrims; they're
+    /// written out with an alpha channel in every [`Format`]    
+    /// variable names: `--primary-90`, `--primary-50`, `--primary-10`
+    Alpha,
+    /// one color per rotation angle, in degrees clockwise; non-finite angles,
+    /// such as `NaN`, are skipped    
+    /// variable names: `--rot-<degrees>` with degrees normalized to `[0, 360)`
+    /// and `.` replaced by `_`, e.g. `--rot-30`, `--rot-22_5`
+    Custom(Vec<f64>),
+    /// a single color rotated the given number of degrees clockwise; a
+    /// non-finite angle, such as `NaN`, emits nothing    
+    /// variable names: `--rotated-<degrees>`, normalized like [`Scheme::Custom`]'s
+    Rotated(f64),
+    /// a [`Scheme::Column`] the given number of points of lightness either side
+    /// of the primary, clamped to `[0, 100]`    
+    /// variable names: `--lighter`, `--darker`
+    SteppedColumn(f64),
+    /// a [`Scheme::Column`] 0.2 either side of the primary in
+    /// [Oklab](https://bottosson.github.io/posts/oklab/) lightness, so both steps
+    /// look equally far from it    
+    /// variable names: `--lighter`, `--darker`
+    PerceptualColumn,
+    /// a [`Scheme::Monochromatic`] with evenly spaced Oklab lightnesses from 0.1
+    /// to 0.9, which look evenly spaced; chroma is reduced wherever the primary's
+    /// doesn't fit in sRGB    
+    /// variable names: `--mono-1`, `--mono-2`, ..., darkest first
+    PerceptualMonochromatic(u8),
+    /// the given number of evenly spaced lightnesses from 10% to 90%, keeping
+    /// hue and saturation (a single step is 50%)    
+    /// variable names: `--mono-1`, `--mono-2`, ..., darkest first
+    Monochromatic(u8),
+    /// the given number of colors, including the primary, spaced evenly around
+    /// the color wheel; a triad is a polyad of 3    
+    /// variable names: `--polyad-1`, `--polyad-2`, ..., clockwise from the primary
+    Polyad(u8),
+}
+
+impl Scheme {
+    /// a [`Scheme::Custom`] rotating the primary by each of `degrees`
+    pub fn from_degrees(degrees: impl IntoIterator<Item = f64>) -> Self {
+        Scheme::Custom(degrees.into_iter().collect())
+    }
+    /// whether the scheme's colors lie on either side of the primary on the
+    /// color wheel, so that a [`Direction`] applies to it
+    pub fn is_directional(&self) -> bool {
+        matches!(
+            self,
+            Scheme::Triad
+                | Scheme::Tetrad
+                | Scheme::Rectangle
+                | Scheme::Analogous
+                | Scheme::SplitComplementary
+                | Scheme::Compound
+                | Scheme::Custom(_)
+                | Scheme::Rotated(_)
+                | Scheme::Polyad(_)
+        )
+    }
+    /// the name the cli uses for the scheme, e.g. `"split-complementary"`, or
+    /// `None` for schemes that need parameters, such as [`Scheme::Custom`]
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Scheme::Column => Some("column"),
+            Scheme::Complementary => Some("complementary"),
+            Scheme::DiagonalComplementary => Some("diagonal-complementary"),
+            Scheme::Triad => Some("triad"),
+            Scheme::Tetrad => Some("tetrad"),
+            Scheme::Rectangle => Some("rectangle"),
+            Scheme::Analogous => Some("analogous"),
+            Scheme::SplitComplementary => Some("split-complementary"),
+            Scheme::Compound => Some("compound"),
+            Scheme::Text => Some("text"),
+            Scheme::Background => Some("background"),
+            Scheme::Neutrals => Some("neutrals"),
+            Scheme::Pastel => Some("pastel"),
+            Scheme::Ramp => Some("ramp"),
+            Scheme::Grayscale => Some("grayscale"),
+            Scheme::Alpha => Some("alpha"),
+            Scheme::Custom(_)
+            | Scheme::Rotated(_)
+            | Scheme::SteppedColumn(_)
+            | Scheme::PerceptualColumn
+            | Scheme::PerceptualMonochromatic(_)
+            | Scheme::Monochromatic(_)
+            | Scheme::Polyad(_) => None,
+        }
+    }
+}
+
+/// parse a scheme by the name the cli uses for it, e.g. `triad` or
+/// `split-complementary`
+///
+/// case is ignored and `_` is accepted in place of `-`; schemes that need
+/// parameters, such as [`Scheme::Custom`], have no name
+impl std::str::FromStr for Scheme {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase().replace('_', "-");
+        NAMED_SCHEMES
+            .into_iter()
+            .find(|scheme| scheme.name() == Some(name.as_str()))
+            .ok_or_else(|| format!("unknown scheme `{}`", s))
+    }
+}
+
+/// every scheme with a [`Scheme::name`]
+const NAMED_SCHEMES: [Scheme; 16] = [
+    Scheme::Column,
+    Scheme::Complementary,
+    Scheme::DiagonalComplementary,
+    Scheme::Triad,
+    Scheme::Tetrad,
+    Scheme::Rectangle,
+    Scheme::Analogous,
+    Scheme::SplitComplementary,
+    Scheme::Compound,
+    Scheme::Text,
+    Scheme::Background,
+    Scheme::Neutrals,
+    Scheme::Pastel,
+    Scheme::Ramp,
+    Scheme::Grayscale,
+    Scheme::Alpha,
+];
+
+/// which side of the primary on the color wheel directional schemes emit colors from
+///
+/// a color's side is the direction of the shorter way around the wheel to it, so
+/// a color exactly opposite the primary is on both sides
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Direction {
+    Clockwise,
+    Counterclockwise,
+    Both,
+}
+
+impl Direction {
+    /// whether a color rotated `rotation` degrees (from `-180.0` to `180.0`)
+    /// from the primary is on this side
+    fn includes(&self, rotation: f64) -> bool {
+        match self {
+            Direction::Clockwise => rotation >= 0.0,
+            Direction::Counterclockwise => rotation <= 0.0 || rotation == 180.0,
+            Direction::Both => true,
+        }
+    }
+}
+
+type ColorVar = (String, Hsl);
+
+/// the iterator over a scheme's variables, see [`ColorScheme::variables`]
+pub type Variables<'a> = std::iter::Chain<
+    std::iter::Once<(&'a str, &'a Hsl)>,
+    std::iter::Map<std::slice::Iter<'a, ColorVar>, fn(&'a ColorVar) -> (&'a str, &'a Hsl)>,
+>;
+
+/// CSS keywords that are valid in place of a color and passed through unchanged
+const KEYWORDS: [&str; 6] = [
+    "currentColor",
+    "inherit",
+    "initial",
+    "unset",
+    "revert",
+    "revert-layer",
+];
+
+/// a value emitted in place of a generated color
+#[derive(Debug, Clone, PartialEq)]
+pub enum Override {
+    /// a replacement color, serialized like any other
+    Color(Hsl),
+    /// a CSS keyword (e.g. `currentColor`, `inherit`) emitted verbatim
+    Keyword(String),
+}
+
+impl Override {
+    /// a [`Override::Keyword`] if `value` is `currentColor` or a CSS-wide keyword
+    /// (case-insensitive)
+    pub fn keyword(value: &str) -> Option<Self> {
+        KEYWORDS
+            .iter()
+            .find(|kw| kw.eq_ignore_ascii_case(value))
+            .map(|kw| Override::Keyword(kw.to_string()))
+    }
+}
+
+/// hue spacings considered harmonious by [`ColorScheme::harmony_score`]
+const HARMONIC_ANGLES: [f64; 7] = [0.0, 30.0, 60.0, 90.0, 120.0, 150.0, 180.0];
+
+/// effective chroma below which [`saturation_drifted`] considers a color's hue lost
+const DRIFT_CHROMA: f64 = 5.0;
+/// saturation below which a color is considered deliberately muted rather than drifted
+const DRIFT_MIN_SATURATION: f64 = 20.0;
+
+/// contrast ratio the `prefers-contrast: more` block pushes text and background
+/// apart towards: the maximum possible, black on white
+const HIGH_CONTRAST_TARGET: f64 = 21.0;
+
+/// contrast ratio [`Scheme::Text`] and [`Scheme::Background`] always have with
+/// each other: WCAG AA for normal text
+const TEXT_CONTRAST_TARGET: f64 = 4.5;
+
+/// how far apart saturation and lightness can be for [`dedup_hues`] to merge colors
+const DEDUP_SL_TOLERANCE: f64 = 5.0;
+
+/// how colors are written out
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Format {
+    /// `#rrggbb`, or `#rrggbbaa` for translucent colors
+    Hex,
+    /// `rgb(r, g, b)`, or `rgba(r, g, b, a)` for translucent colors
+    Rgb,
+    /// `hsl(h, s%, l%)`, or `hsla(h, s%, l%, a)` for translucent colors
+    Hsl,
+    /// `oklch(l% c h)`, or `oklch(l% c h / a)` for translucent colors
+    Oklch,
+}
+
+/// the kind of document a scheme is rendered as
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum OutputFormat {
+    /// CSS custom properties, see [`ColorScheme::as_css`]
+    Css,
+    /// CSS custom properties using `light-dark()`, see [`ColorScheme::as_light_dark`]
+    LightDark,
+    /// a Tailwind v4 `@theme` block, see [`ColorScheme::as_tailwind_theme`]
+    TailwindTheme,
+    /// a Tailwind `theme.extend.colors` object, see [`ColorScheme::as_tailwind`]
+    TailwindConfig,
+    /// a JSON object, see [`ColorScheme::as_json`]
+    Json,
+    /// Sass variables, see [`ColorScheme::as_scss`]
+    Scss,
+    /// an HTML page of swatches, see [`ColorScheme::as_html_preview`]
+    Html,
 }
 
-type ColorVar = (&'static str, Hsl);
+/// the order variables are written out in
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Order {
+    /// the primary, then each scheme's colors in the order the schemes were added
+    Insertion,
+    /// darkest to lightest by relative luminance, including the primary
+    Luminance,
+    /// the primary, then the other colors alphabetically by variable name, for
+    /// output that diffs cleanly
+    Alphabetical,
+    /// three groups, each in insertion order: the primary and the colors that
+    /// share its hue (columns, ramps, neutrals, and so on), then the hue
+    /// rotations (complementary, triad, tetrad, and so on), then the
+    /// `--text-*` and `--background-*` roles; `<name>-text` pairs stay with `<name>`
+    Canonical,
+}
 
 /// a colorscheme with a primary color and one or more additional colors
+///
+/// colors passed in are clamped to a saturation and lightness between `0.0` and
+/// `100.0` (a `NaN` counts as `0.0`) and an alpha between `0.0` and `1.0` (a `NaN`
+/// alpha counts as opaque); a missing alpha and `Some(1.0)` both mean opaque,
+/// and only translucent colors are written out with an alpha channel
 #[derive(Debug, Clone, PartialEq)]
 pub struct ColorScheme {
     primary: Hsl,
     colors: Vec<ColorVar>,
+    overrides: Vec<(String, Override)>,
+    excluded: Vec<String>,
+    prefix: Option<String>,
+    primary_name: String,
+    include_primary: bool,
+    format: Format,
+    keep_360: bool,
+    high_contrast: bool,
+    order: Order,
+    dark_mode: bool,
+    direction: Direction,
+    color_names: bool,
+    text_pairs: bool,
 }
 
 impl ColorScheme {
     /// create a colorscheme from a primary color and scheme variant
     pub fn new(primary: Hsl, scheme: Scheme) -> Self {
-        let colors = ColorScheme::colors(&primary, scheme);
-        Self { primary, colors }
+        Self::from_schemes(primary, [scheme])
+    }
+    /// [`ColorScheme::new`] for an RGB primary
+    pub fn from_rgb(primary: colorsys::Rgb, scheme: Scheme) -> Self {
+        Self::new(Hsl::from(primary), scheme)
     }
+    /// [`ColorScheme::from_schemes`] for an RGB primary
+    pub fn from_rgb_schemes(
+        primary: colorsys::Rgb,
+        schemes: impl IntoIterator<Item = Scheme>,
+    ) -> Self {
+        Self::from_schemes(Hsl::from(primary), schemes)
+    }
+    /// create a colorscheme from a primary color and several scheme variants
+    ///
+    /// when more than one generates the same variable name, the first wins; with
+    /// no schemes at all, the colorscheme is just the primary
     pub fn from_schemes(primary: Hsl, schemes: impl IntoIterator<Item = Scheme>) -> Self {
+        Self::from_schemes_in(primary, schemes, Direction::Both)
+    }
+    /// like [`ColorScheme::from_schemes`], but directional schemes only emit the
+    /// colors on the `direction` side of the primary
+    pub fn from_schemes_in(
+        primary: Hsl,
+        schemes: impl IntoIterator<Item = Scheme>,
+        direction: Direction,
+    ) -> Self {
+        let primary = clamp_color(primary);
         let mut colors = Vec::new();
         for scheme in schemes {
-            colors.extend(ColorScheme::colors(&primary, scheme));
+            extend_unique(
+                &mut colors,
+                ColorScheme::colors(&primary, scheme, direction),
+            );
+        }
+        Self {
+            primary,
+            colors,
+            overrides: Vec::new(),
+            excluded: Vec::new(),
+            prefix: None,
+            primary_name: String::from("--primary"),
+            include_primary: true,
+            format: Format::Hex,
+            keep_360: false,
+            high_contrast: false,
+            order: Order::Insertion,
+            dark_mode: false,
+            direction,
+            color_names: false,
+            text_pairs: false,
+        }
+    }
+    /// a ramp of `steps` colors interpolated from `from` to `to`, named
+    /// `--step-0` (`from`) through `--step-<steps - 1>` (`to`)
+    ///
+    /// hues are interpolated along the shortest arc; a `steps` below `2` is
+    /// treated as `2`, since a ramp needs both ends
+    pub fn between(from: Hsl, to: Hsl, steps: usize) -> Self {
+        let (from, to) = (clamp_color(from), clamp_color(to));
+        let last = steps.max(2) - 1;
+        let colors = (0..=last)
+            .map(|i| {
+                let t = i as f64 / last as f64;
+                (format!("--step-{}", i), lerp(&from, &to, t))
+            })
+            .collect();
+        Self {
+            colors,
+            ..Self::from_schemes(from, []).without_primary()
+        }
+    }
+    /// read back the variables of the first block in a stylesheet, such as one
+    /// written by [`ColorScheme::as_css`], with `--primary` as the primary
+    ///
+    /// values can be anything [`parse_color`] accepts; anything
+    /// outside the block is ignored. colors come back as they were written, so
+    /// a round trip through the default hex [`Format`] rounds them to whole
+    /// RGB channels
+    ///
+    /// the primary must be named `--primary`; for a scheme written with
+    /// [`ColorScheme::with_primary_name`] or [`ColorScheme::with_prefix`], use
+    /// [`ColorScheme::from_css_with_primary`]
+    pub fn from_css(input: &str) -> Result<Self, String> {
+        Self::from_css_with_primary(input, "--primary")
+    }
+    /// [`ColorScheme::from_css`] with the variable `primary_name` as the primary
+    ///
+    /// the leading `--` of `primary_name` is optional. prefixes aren't recognized,
+    /// so reading back the output of [`ColorScheme::with_prefix`] keeps them
+    /// as part of every name, e.g. `--brand-primary`
+    pub fn from_css_with_primary(input: &str, primary_name: &str) -> Result<Self, String> {
+        let primary_name = var_name(primary_name);
+        let (_, rest) = input.split_once('{').ok_or("no `{` block found")?;
+        let (body, _) = rest.split_once('}').ok_or("unclosed `{` block")?;
+        let mut primary = None;
+        let mut colors = Vec::new();
+        for declaration in body.split(';').map(str::trim).filter(|d| !d.is_empty()) {
+            let (name, value) = declaration
+                .split_once(':')
+                .ok_or_else(|| format!("expected `--name: value`, got `{}`", declaration))?;
+            let name = name.trim();
+            if !is_custom_property_name(name) {
+                return Err(format!("`{}` is not a custom property name", name));
+            }
+            let color = parse_color(value).map_err(|e| format!("{}: {}", name, e))?;
+            if name == primary_name {
+                primary = Some(color);
+            } else {
+                colors.push((name.to_string(), color));
+            }
         }
-        Self { primary, colors }
+        let primary = primary.ok_or_else(|| format!("no `{}` variable found", primary_name))?;
+        Ok(Self {
+            colors,
+            ..Self::from_schemes(primary, []).with_primary_name(&primary_name)
+        })
+    }
+    /// the primary and a single color rotated `degrees` clockwise, named
+    /// `--rotated-<degrees>`
+    ///
+    /// angles outside `[0, 360)` wrap around the color wheel; a non-finite
+    /// angle leaves just the primary
+    pub fn rotated(primary: Hsl, degrees: f64) -> Self {
+        Self::new(primary, Scheme::Rotated(degrees))
     }
     /// add another scheme variant's colors
+    ///
+    /// colors whose names the scheme already has are skipped, so the first
+    /// scheme to generate a name wins
     pub fn and(mut self, scheme: Scheme) -> Self {
+        let colors = ColorScheme::colors(&self.primary, scheme, self.direction);
+        extend_unique(&mut self.colors, colors);
+        self
+    }
+    /// add every scheme variant's colors, in order
+    pub fn and_schemes(self, schemes: impl IntoIterator<Item = Scheme>) -> Self {
+        schemes.into_iter().fold(self, ColorScheme::and)
+    }
+    /// add `other`'s colors after this scheme's
+    ///
+    /// as with [`ColorScheme::and`], colors whose names this scheme already has
+    /// are skipped. the primary and every setting (prefix, format, overrides,
+    /// and so on) are this scheme's; `other`'s primary is dropped
+    pub fn merge(mut self, other: ColorScheme) -> Self {
+        extend_unique(&mut self.colors, other.colors);
+        self
+    }
+    /// emit `value` for the variable `name` instead of its generated color
+    ///
+    /// the leading `--` of `name` is optional; overrides for variables the scheme
+    /// doesn't generate are ignored
+    pub fn with_override(mut self, name: &str, value: Override) -> Self {
+        let name = var_name(name);
+        let value = match value {
+            Override::Color(color) => Override::Color(clamp_color(color)),
+            keyword => keyword,
+        };
+        self.overrides.push((name, value));
+        self
+    }
+    /// namespace every emitted variable with `prefix`, e.g. `--brand-primary`
+    /// for a prefix of `brand`
+    ///
+    /// overrides, exclusions, and [`ColorScheme::get`] still use the unprefixed names
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        let prefix = prefix.trim_start_matches("--").trim_end_matches('-');
+        self.prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+        self
+    }
+    /// name the primary's variable `name` instead of `--primary`
+    ///
+    /// the leading `--` of `name` is optional
+    pub fn with_primary_name(mut self, name: &str) -> Self {
+        self.primary_name = var_name(name);
+        self
+    }
+    /// leave the variable `name` out of the output
+    ///
+    /// the leading `--` of `name` is optional
+    pub fn excluding(mut self, name: &str) -> Self {
+        self.excluded.push(var_name(name));
+        self
+    }
+    /// whether both schemes have the same variables, in the same order, with the
+    /// same hex values
+    ///
+    /// unlike `==`, colors that differ only by floating-point error below what hex
+    /// can show are considered equal, and output options aren't compared
+    pub fn eq_rendered(&self, other: &ColorScheme) -> bool {
+        self.variables()
+            .map(|(name, color)| (name, hsl_to_css(color)))
+            .eq(other
+                .variables()
+                .map(|(name, color)| (name, hsl_to_css(color))))
+    }
+    /// look up a color by its exact variable name, including the primary's
+    pub fn get(&self, name: &str) -> Option<&Hsl> {
+        self.variables()
+            .find(|(var_name, _)| *var_name == name)
+            .map(|(_, color)| color)
+    }
+    /// the largest [`delta_e`] between same-named variables of two schemes
+    ///
+    /// variables only present in one of the schemes are ignored
+    pub fn max_delta(&self, other: &ColorScheme) -> f64 {
+        self.variables()
+            .filter_map(|(name, color)| other.get(name).map(|c| delta_e(color, c)))
+            .fold(0.0, f64::max)
+    }
+    /// follow every emitted color with a black or white `<name>-text` variable,
+    /// whichever contrasts more
+    ///
+    /// pairs are worked out on output, so colors added afterwards get them too,
+    /// and colors that aren't emitted, such as a primary hidden by
+    /// [`ColorScheme::without_primary`], don't
+    pub fn with_text_pairs(mut self) -> Self {
+        self.text_pairs = true;
+        self
+    }
+    /// write colors out in `format` (default: [`Format::Hex`])
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+    /// emit a hue of exactly 360 as `360` instead of normalizing it to `0`
+    /// in [`Format::Hsl`] output
+    pub fn keeping_360(mut self) -> Self {
+        self.keep_360 = true;
+        self
+    }
+    /// how close the hue spacing between every pair of colors is to a harmonic
+    /// angle (0, 30, 60, 90, 120, 150, or 180 degrees)
+    ///
+    /// ranges from `0.0` (every pair is as far from a harmonic angle as
+    /// possible) to `1.0` (every pair is exactly harmonic)    
+    /// this is subjective and mostly useful for comparing alternatives    
+    /// a primary hidden by [`ColorScheme::without_primary`] isn't counted
+    pub fn harmony_score(&self) -> f64 {
+        let hues: Vec<f64> = self
+            .variables()
+            .skip(if self.include_primary { 0 } else { 1 })
+            .map(|(_, color)| color.hue())
+            .collect();
+        let mut deviations = Vec::new();
+        for (i, a) in hues.iter().enumerate() {
+            for b in hues[i + 1..].iter() {
+                let distance = Hue::new(*a).distance(Hue::new(*b));
+                let deviation = HARMONIC_ANGLES
+                    .iter()
+                    .map(|angle| (distance - angle).abs())
+                    .fold(f64::INFINITY, f64::min);
+                deviations.push(deviation);
+            }
+        }
+        if deviations.is_empty() {
+            return 1.0;
+        }
+        let mean = deviations.iter().sum::<f64>() / deviations.len() as f64;
+        // harmonic angles are 30 degrees apart, so no distance is ever more
+        // than 15 degrees from one
+        (1.0 - mean / 15.0).clamp(0.0, 1.0)
+    }
+    /// a warning for every color whose lightness has become so extreme that
+    /// its hue is no longer recognizable; a primary hidden by
+    /// [`ColorScheme::without_primary`] isn't checked
+    pub fn warnings(&self) -> Vec<String> {
+        self.variables()
+            .skip(if self.include_primary { 0 } else { 1 })
+            .filter(|(_, color)| saturation_drifted(color))
+            .map(|(name, color)| {
+                format!(
+                    "`{}` has lost its hue at {}% lightness (effective chroma {:.1}%)",
+                    name,
+                    color.lightness(),
+                    effective_chroma(color)
+                )
+            })
+            .collect()
+    }
+    /// whether `--text-primary` is readable on `--background-primary` under WCAG AA
+    ///
+    /// `false` unless the scheme includes both the [`Scheme::Text`] and
+    /// [`Scheme::Background`] colors
+    pub fn meets_wcag_aa(&self) -> bool {
+        match (self.get("--text-primary"), self.get("--background-primary")) {
+            (Some(text), Some(background)) => ContrastStandard::Aa.passes(text, background),
+            _ => false,
+        }
+    }
+    /// a warning for every text color that isn't readable on its background
+    /// under `standard`
+    ///
+    /// `--text-primary` is checked against `--background-primary`, and every
+    /// `<name>-text` against `<name>`
+    pub fn contrast_failures(&self, standard: ContrastStandard) -> Vec<String> {
+        let vars = self.ordered_variables();
+        let get = |name: &str| {
+            vars.iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, color)| color.hsl())
+                .or_else(|| self.get(name))
+        };
+        let mut pairs: Vec<(&str, &str)> = vars
+            .iter()
+            .filter_map(|(name, _)| name.strip_suffix("-text").map(|bg| (name.as_str(), bg)))
+            .collect();
+        pairs.insert(0, ("--text-primary", "--background-primary"));
+        pairs
+            .into_iter()
+            .filter_map(|(text, background)| {
+                let (t, b) = (get(text)?, get(background)?);
+                (!standard.passes(t, b)).then(|| {
+                    format!(
+                        "`{}` on `{}` has a contrast of {:.2}, below the {} minimum of {}",
+                        text,
+                        background,
+                        standard.contrast(t, b),
+                        standard,
+                        standard.threshold()
+                    )
+                })
+            })
+            .collect()
+    }
+    /// a line for every generated color describing how it was derived from
+    /// the primary: the hue rotation applied and any saturation or lightness change
+    pub fn explain(&self) -> Vec<String> {
+        let primary = &self.primary;
         self.colors
-            .extend(ColorScheme::colors(&self.primary, scheme));
+            .iter()
+            .map(|(name, color)| {
+                let mut line = format!(
+                    "{}: hue {} {:+} = {}",
+                    name,
+                    tenths(primary.hue()),
+                    tenths(signed_rotation(primary.hue(), color.hue())),
+                    tenths(color.hue()),
+                );
+                for (label, from, to) in [
+                    ("saturation", primary.saturation(), color.saturation()),
+                    ("lightness", primary.lightness(), color.lightness()),
+                ] {
+                    if tenths(from) != tenths(to) {
+                        let _ = write!(line, ", {} {}% -> {}%", label, tenths(from), tenths(to));
+                    }
+                }
+                let _ = write!(line, " ({})", hsl_to_css(color));
+                line
+            })
+            .collect()
+    }
+    /// also emit a `@media (prefers-contrast: more)` block in which
+    /// `--text-primary` and `--background-primary` are pushed apart to maximum
+    /// contrast
+    ///
+    /// has no effect unless the scheme includes both the [`Scheme::Text`] and
+    /// [`Scheme::Background`] colors
+    pub fn with_high_contrast(mut self) -> Self {
+        self.high_contrast = true;
+        self
+    }
+    /// check that every emitted variable name is a valid CSS custom property
+    /// name: `--` followed by letters, digits, `-`, `_`, or non-ASCII characters
+    pub fn validate(&self) -> Result<(), String> {
+        match self
+            .entries()
+            .into_iter()
+            .find(|(name, _)| !is_custom_property_name(name))
+        {
+            Some((name, _)) => Err(format!("invalid CSS custom property name `{}`", name)),
+            None => Ok(()),
+        }
+    }
+    /// also emit a `@media (prefers-color-scheme: dark)` block with the colors of
+    /// [`ColorScheme::dark`]
+    pub fn with_dark_mode(mut self) -> Self {
+        self.dark_mode = true;
+        self
+    }
+    /// a dark-mode counterpart of this scheme, with every color's lightness inverted
+    pub fn dark(&self) -> ColorScheme {
+        let darken = |color: &Hsl| with_lightness(color, invert(color.lightness()));
+        ColorScheme {
+            primary: darken(&self.primary),
+            colors: self
+                .colors
+                .iter()
+                .map(|(name, color)| (name.clone(), darken(color)))
+                .collect(),
+            dark_mode: false,
+            ..self.clone()
+        }
+    }
+    /// the text and background colors adjusted for high contrast, if the scheme has them
+    fn high_contrast_colors(&self) -> Option<Vec<ColorVar>> {
+        let text = self.get("--text-primary")?;
+        let background = self.get("--background-primary")?;
+        let (text, background) = solve_contrast(text, background, HIGH_CONTRAST_TARGET);
+        Some(vec![
+            (String::from("--text-primary"), text),
+            (String::from("--background-primary"), background),
+        ])
+    }
+    /// follow each declaration in [`ColorScheme::as_css`]'s main block with a
+    /// comment naming the closest CSS color, e.g. `--complementary: #00ffff; /* aqua */`
+    #[cfg(feature = "named-colors")]
+    pub fn with_color_names(mut self) -> Self {
+        self.color_names = true;
+        self
+    }
+    /// write variables out in `order` (default: [`Order::Insertion`])
+    pub fn ordered_by(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+    /// shorthand for [`ColorScheme::ordered_by`] [`Order::Canonical`]
+    pub fn canonically_ordered(self) -> Self {
+        self.ordered_by(Order::Canonical)
+    }
+    /// shorthand for [`ColorScheme::ordered_by`] [`Order::Alphabetical`]
+    pub fn sorted(self) -> Self {
+        self.ordered_by(Order::Alphabetical)
+    }
+    /// omit the primary's variable from the output
+    pub fn without_primary(mut self) -> Self {
+        self.include_primary = false;
         self
     }
-    fn colors(primary: &Hsl, scheme: Scheme) -> Vec<ColorVar> {
+    fn colors(primary: &Hsl, scheme: Scheme, direction: Direction) -> Vec<ColorVar> {
+        let mut colors = Self::scheme_colors(primary, &scheme);
+        if scheme.is_directional() {
+            colors.retain(|(_, color)| {
+                direction.includes(signed_rotation(primary.hue(), color.hue()))
+            });
+        }
+        colors
+    }
+    fn scheme_colors(primary: &Hsl, scheme: &Scheme) -> Vec<ColorVar> {
         match scheme {
             Scheme::Column => Self::column(primary),
             Scheme::Complementary => Self::complementary(primary),
             Scheme::DiagonalComplementary => Self::diagonal_complementary(primary),
             Scheme::Triad => Self::triad(primary),
             Scheme::Tetrad => Self::tetrad(primary),
+            Scheme::Rectangle => Self::rectangle(primary),
+            Scheme::Analogous => Self::analogous(primary),
+            Scheme::SplitComplementary => Self::split_complementary(primary),
+            Scheme::Compound => Self::compound(primary),
             Scheme::Text => Self::text(primary),
             Scheme::Background => Self::background(primary),
+            Scheme::Neutrals => Self::neutrals(primary),
+            Scheme::Pastel => Self::pastel(primary),
+            Scheme::Ramp => Self::ramp(primary),
+            Scheme::Grayscale => Self::grayscale(primary),
+            Scheme::Alpha => Self::alpha(primary),
+            Scheme::Custom(degrees) => Self::custom(primary, degrees),
+            Scheme::SteppedColumn(step) => Self::stepped_column(primary, *step),
+            Scheme::PerceptualColumn => Self::perceptual_column(primary),
+            Scheme::PerceptualMonochromatic(steps) => {
+                Self::perceptual_monochromatic(primary, *steps)
+            }
+            Scheme::Rotated(by) if by.is_finite() => {
+                vec![(rotation_name("rotated", *by), rotate(primary, *by))]
+            }
+            Scheme::Rotated(_) => Vec::new(),
+            Scheme::Monochromatic(steps) => Self::monochromatic(primary, *steps),
+            Scheme::Polyad(n) => Self::polyad(primary, *n),
         }
     }
     /// serialize the scheme to CSS variables defined under the provided selector or `:root`
     ///
-    /// all colors are converted to RGB hex strings
+    /// colors are written out in the scheme's [`Format`]; a blank selector, or one
+    /// with a `{` or `}` that would break the block, falls back to `:root`
     pub fn as_css(&self, selector: Option<&str>) -> String {
-        let sel = selector.unwrap_or(":root");
-        let mut s = format!("{} {{", sel);
-        write!(s, "\n\t--primary: {};", hsl_to_css(&self.primary)).unwrap();
-        for (var_name, color) in self.colors.iter() {
-            write!(s, "\n\t{}: {};", var_name, hsl_to_css(color)).unwrap();
+        self.css(selector, Layout::PRETTY)
+    }
+    /// [`ColorScheme::as_css`] on a single line with no optional whitespace, e.g.
+    /// `:root{--primary:#ff0000;--complementary:#00ffff}`
+    ///
+    /// color names from [`ColorScheme::with_color_names`] are left out
+    pub fn as_css_minified(&self, selector: Option<&str>) -> String {
+        self.css(selector, Layout::MINIFIED)
+    }
+    /// the variables under `selector`, then any high contrast and dark mode
+    /// blocks, laid out with `layout`
+    fn css(&self, selector: Option<&str>, layout: Layout) -> String {
+        let sel = css_selector(selector);
+        let mut s = String::new();
+        let declarations = self
+            .ordered_variables()
+            .into_iter()
+            .map(|(name, color)| {
+                (
+                    self.output_name(&name),
+                    self.css_value(&name, &color),
+                    self.color_name(&name, &color),
+                )
+            })
+            .collect();
+        write_block(&mut s, layout, 0, sel, declarations);
+        if let Some(colors) = self
+            .high_contrast
+            .then(|| self.high_contrast_colors())
+            .flatten()
+        {
+            let entries = colors
+                .iter()
+                .filter(|(name, _)| !self.is_excluded(name))
+                .map(|(name, color)| {
+                    (
+                        self.output_name(name),
+                        self.css_value(name, &Color::from(color)),
+                    )
+                })
+                .collect();
+            write_media_block(&mut s, layout, ("prefers-contrast", "more"), sel, entries);
+        }
+        if self.dark_mode {
+            let dark = self.dark().entries();
+            write_media_block(&mut s, layout, ("prefers-color-scheme", "dark"), sel, dark);
+        }
+        s
+    }
+    /// a block under the same selector declaring every color as plain hex,
+    /// followed by [`ColorScheme::as_css`], so that browsers that don't
+    /// understand the main block's values keep the hex ones
+    ///
+    /// variables overridden with a keyword are left out of the hex block
+    pub fn as_css_with_fallbacks(&self, selector: Option<&str>) -> String {
+        let hex = ColorScheme {
+            format: Format::Hex,
+            ..self.clone()
+        };
+        let declarations = hex
+            .ordered_variables()
+            .into_iter()
+            .filter(|(name, _)| {
+                !matches!(
+                    self.overrides.iter().rev().find(|(n, _)| n == name),
+                    Some((_, Override::Keyword(_)))
+                )
+            })
+            .map(|(name, color)| (hex.output_name(&name), hex.css_value(&name, &color), None))
+            .collect();
+        let mut s = String::new();
+        write_block(
+            &mut s,
+            Layout::PRETTY,
+            0,
+            css_selector(selector),
+            declarations,
+        );
+        s.push('\n');
+        s.push_str(&self.as_css(selector));
+        s
+    }
+    /// [`ColorScheme::as_css`], followed by the variables of `dark` in a
+    /// `@media (prefers-color-scheme: dark)` block
+    ///
+    /// `dark` is usually [`ColorScheme::dark`], which is what
+    /// [`ColorScheme::with_dark_mode`] uses
+    pub fn as_css_with_dark(&self, dark: &ColorScheme, selector: Option<&str>) -> String {
+        let light = ColorScheme {
+            dark_mode: false,
+            ..self.clone()
+        };
+        let mut s = light.as_css(selector);
+        let sel = css_selector(selector);
+        let query = ("prefers-color-scheme", "dark");
+        write_media_block(&mut s, Layout::PRETTY, query, sel, dark.entries());
+        s
+    }
+    /// serialize the scheme to CSS variables under the provided selector or `:root`,
+    /// each pairing its color with its [`ColorScheme::dark`] counterpart in a CSS
+    /// [`light-dark()`](https://developer.mozilla.org/en-US/docs/Web/CSS/color_value/light-dark)
+    /// expression
+    ///
+    /// `color-scheme: light dark;` is declared as well, since `light-dark()`
+    /// depends on it
+    pub fn as_light_dark(&self, selector: Option<&str>) -> String {
+        let sel = css_selector(selector);
+        let dark = self.dark();
+        let dark_vars = dark.ordered_variables();
+        let mut s = format!("{} {{\n\tcolor-scheme: light dark;", sel);
+        for (var_name, color) in self.ordered_variables() {
+            // safety: `dark` emits the same variables as `self`
+            let (_, dark_color) = dark_vars.iter().find(|(n, _)| *n == var_name).unwrap();
+            let dark_value = dark.css_value(&var_name, dark_color);
+            write!(
+                s,
+                "\n\t{}: light-dark({}, {});",
+                self.output_name(&var_name),
+                self.css_value(&var_name, &color),
+                dark_value
+            )
+            .unwrap();
+        }
+        write!(s, "\n}}").unwrap();
+        s
+    }
+    /// serialize the scheme to a [Tailwind v4](https://tailwindcss.com/docs/theme)
+    /// `@theme` block, prefixing every variable with `--color-` so Tailwind
+    /// generates utilities such as `bg-primary` for it
+    pub fn as_tailwind_theme(&self) -> String {
+        let mut s = String::from("@theme {");
+        for (var_name, value) in self.entries() {
+            write!(
+                s,
+                "\n\t--color-{}: {};",
+                var_name.trim_start_matches("--"),
+                value
+            )
+            .unwrap();
+        }
+        write!(s, "\n}}").unwrap();
+        s
+    }
+    /// serialize the scheme to an object for Tailwind's `theme.extend.colors`,
+    /// keyed by variable name without the leading `--`, e.g.
+    /// `{ "primary": "#ff0000", "complementary": "#00ffff" }`
+    ///
+    /// the object is valid as both JSON and JavaScript
+    pub fn as_tailwind(&self) -> String {
+        let members: Vec<String> = self
+            .entries()
+            .into_iter()
+            .map(|(var_name, value)| {
+                format!(
+                    "{}: {}",
+                    json_string(var_name.trim_start_matches("--")),
+                    json_string(&value)
+                )
+            })
+            .collect();
+        format!("{{ {} }}", members.join(", "))
+    }
+    /// serialize the scheme to a compact JSON object mapping variable names to
+    /// their values, e.g. `{"--primary":"#ff0000","--complementary":"#00ffff"}`
+    pub fn as_json(&self) -> String {
+        let members: Vec<String> = self
+            .entries()
+            .into_iter()
+            .map(|(var_name, value)| format!("{}:{}", json_string(&var_name), json_string(&value)))
+            .collect();
+        format!("{{{}}}", members.join(","))
+    }
+    /// serialize the scheme to [Sass](https://sass-lang.com/documentation/variables/)
+    /// variables, one per line with no wrapping selector, e.g. `$primary: #ff0000;`
+    pub fn as_scss(&self) -> String {
+        self.entries()
+            .into_iter()
+            .map(|(var_name, value)| format!("${}: {};", var_name.trim_start_matches("--"), value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    /// a standalone HTML page with a swatch per variable, labeled with its name
+    /// and hex value, for eyeballing a palette in a browser
+    ///
+    /// swatches show the generated colors; overrides aren't applied
+    pub fn as_html_preview(&self) -> String {
+        let mut s = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>colorscheme</title>\n</head>\n<body style=\"margin: 0; font-family: monospace;\">",
+        );
+        for (var_name, color) in self.ordered_variables() {
+            let hex = hsl_to_css(color.hsl());
+            write!(
+                s,
+                "\n<div style=\"background: {}; color: {}; padding: 2em;\">{} {}</div>",
+                hex,
+                hsl_to_css(&readable_on(color.hsl())),
+                html_escape(&self.output_name(&var_name)),
+                hex
+            )
+            .unwrap();
         }
-        write!(s, "\n}};").unwrap();
+        write!(s, "\n</body>\n</html>").unwrap();
         s
     }
+    /// every emitted variable name and its serialized value, in output order
+    ///
+    /// all output formats should render from this so they agree on which
+    /// variables are present
+    fn entries(&self) -> Vec<(String, String)> {
+        self.ordered_variables()
+            .into_iter()
+            .map(|(name, color)| (self.output_name(&name), self.css_value(&name, &color)))
+            .collect()
+    }
+    /// the variables that are emitted, by generated name, in output order,
+    /// each followed by its [`ColorScheme::with_text_pairs`] pair if set
+    fn ordered_variables(&self) -> Vec<(String, Color)> {
+        let mut vars: Vec<(&str, Color)> = self
+            .variables()
+            .skip(if self.include_primary { 0 } else { 1 })
+            .filter(|(name, _)| !self.is_excluded(name))
+            .map(|(name, color)| (name, Color::from(color)))
+            .collect();
+        match self.order {
+            Order::Insertion => {}
+            Order::Luminance => {
+                vars.sort_by(|(_, a), (_, b)| a.luminance().total_cmp(&b.luminance()))
+            }
+            Order::Alphabetical => {
+                let pinned = vars
+                    .first()
+                    .is_some_and(|(name, _)| *name == self.primary_name);
+                vars[usize::from(pinned)..].sort_by_key(|(name, _)| *name);
+            }
+            Order::Canonical => vars.sort_by_key(|(name, _)| canonical_group(name)),
+        }
+        if !self.text_pairs {
+            return vars
+                .into_iter()
+                .map(|(name, color)| (name.to_string(), color))
+                .collect();
+        }
+        vars.into_iter()
+            .flat_map(|(name, color)| {
+                let text = Color::from(&readable_on(color.hsl()));
+                [(name.to_string(), color), (format!("{}-text", name), text)]
+            })
+            .filter(|(name, _)| !self.is_excluded(name))
+            .collect()
+    }
+    /// the name a variable is emitted under, taking the prefix into account
+    fn output_name(&self, name: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("--{}-{}", prefix, name.trim_start_matches("--")),
+            None => name.to_string(),
+        }
+    }
+    fn is_excluded(&self, name: &str) -> bool {
+        self.excluded.iter().any(|excluded| excluded == name)
+    }
+    /// the primary followed by every generated color, by variable name, in the
+    /// order they were generated
+    ///
+    /// these are the colors themselves: output-only options such as overrides,
+    /// exclusions, [`ColorScheme::without_primary`], and [`Order`] don't apply
+    pub fn variables(&self) -> Variables<'_> {
+        let by_name: fn(&ColorVar) -> (&str, &Hsl) = |(name, color)| (name.as_str(), color);
+        std::iter::once((self.primary_name.as_str(), &self.primary))
+            .chain(self.colors.iter().map(by_name))
+    }
+    /// the CSS color name closest to a variable's emitted color, if
+    /// [`ColorScheme::with_color_names`] is set and the variable's value is a color
+    #[cfg(feature = "named-colors")]
+    fn color_name(&self, name: &str, color: &Color) -> Option<&'static str> {
+        if !self.color_names {
+            return None;
+        }
+        match self.overrides.iter().rev().find(|(n, _)| n == name) {
+            Some((_, Override::Keyword(_))) => None,
+            Some((_, Override::Color(c))) => Some(nearest_named_color(c)),
+            None => Some(nearest_named_color(color.hsl())),
+        }
+    }
+    #[cfg(not(feature = "named-colors"))]
+    fn color_name(&self, _name: &str, _color: &Color) -> Option<&'static str> {
+        None
+    }
+    /// the serialized value of a variable, taking overrides into account
+    fn css_value(&self, name: &str, color: &Color) -> String {
+        // later overrides take precedence
+        match self.overrides.iter().rev().find(|(n, _)| n == name) {
+            Some((_, Override::Keyword(keyword))) => keyword.clone(),
+            Some((_, Override::Color(c))) => self.serialize(&Color::from(c)),
+            None => self.serialize(color),
+        }
+    }
+    fn serialize(&self, color: &Color) -> String {
+        serialize(color, self.format, self.keep_360)
+    }
     fn column(primary: &Hsl) -> Vec<ColorVar> {
         let lightness = primary.lightness();
         let lighter = with_lightness(primary, lightness * 1.5);
         let darker = with_lightness(primary, lightness * 0.5);
-        vec![("--lighter", lighter), ("--darker", darker)]
+        vec![
+            (String::from("--lighter"), lighter),
+            (String::from("--darker"), darker),
+        ]
+    }
+    fn stepped_column(primary: &Hsl, step: f64) -> Vec<ColorVar> {
+        let lightness = primary.lightness();
+        vec![
+            (
+                String::from("--lighter"),
+                with_lightness(primary, lightness + step),
+            ),
+            (
+                String::from("--darker"),
+                with_lightness(primary, lightness - step),
+            ),
+        ]
     }
     fn complementary(primary: &Hsl) -> Vec<ColorVar> {
         let complementary = rotate(primary, 180.0);
-        vec![("--complementary", complementary)]
+        vec![(String::from("--complementary"), complementary)]
     }
     fn diagonal_complementary(primary: &Hsl) -> Vec<ColorVar> {
         let new_saturation = invert(primary.saturation());
@@ -102,77 +1155,941 @@ impl ColorScheme {
         let diagonal_complementary = rotate(primary, 180.0);
         let diagonal_complementary = with_saturation(&diagonal_complementary, new_saturation);
         let diagonal_complementary = with_lightness(&diagonal_complementary, new_lightness);
-        vec![("--diagonal-complementary", diagonal_complementary)]
+        vec![(
+            String::from("--diagonal-complementary"),
+            diagonal_complementary,
+        )]
     }
     fn triad(primary: &Hsl) -> Vec<ColorVar> {
         let clockwise = rotate(primary, 120.0);
         let counterclockwise = rotate(primary, -120.0);
         vec![
-            ("--clockwise", clockwise),
-            ("--counterclockwise", counterclockwise),
+            (String::from("--clockwise"), clockwise),
+            (String::from("--counterclockwise"), counterclockwise),
+        ]
+    }
+    fn analogous(primary: &Hsl) -> Vec<ColorVar> {
+        let clockwise = rotate(primary, 30.0);
+        let counterclockwise = rotate(primary, -30.0);
+        vec![
+            (String::from("--analogous-clockwise"), clockwise),
+            (
+                String::from("--analogous-counterclockwise"),
+                counterclockwise,
+            ),
+        ]
+    }
+    fn split_complementary(primary: &Hsl) -> Vec<ColorVar> {
+        let clockwise = rotate(primary, 150.0);
+        let counterclockwise = rotate(primary, 210.0);
+        vec![
+            (String::from("--split-complementary-clockwise"), clockwise),
+            (
+                String::from("--split-complementary-counterclockwise"),
+                counterclockwise,
+            ),
         ]
     }
+    fn compound(primary: &Hsl) -> Vec<ColorVar> {
+        let mut colors = Self::complementary(primary);
+        colors.extend([
+            (String::from("--compound-clockwise"), rotate(primary, 150.0)),
+            (
+                String::from("--compound-counterclockwise"),
+                rotate(primary, 210.0),
+            ),
+        ]);
+        colors
+    }
     fn tetrad(primary: &Hsl) -> Vec<ColorVar> {
         let by: f64 = 90.0;
         let upper_right = rotate(primary, by);
         let lower_right = rotate(&upper_right, by);
         let lower_left = rotate(&lower_right, by);
         vec![
-            ("--upper-right", upper_right),
-            ("--lower-right", lower_right),
-            ("--lower-left", lower_left),
+            (String::from("--upper-right"), upper_right),
+            (String::from("--lower-right"), lower_right),
+            (String::from("--lower-left"), lower_left),
+        ]
+    }
+    fn rectangle(primary: &Hsl) -> Vec<ColorVar> {
+        vec![
+            (String::from("--rect-upper-right"), rotate(primary, 60.0)),
+            (String::from("--rect-lower-right"), rotate(primary, 180.0)),
+            (String::from("--rect-lower-left"), rotate(primary, 240.0)),
         ]
     }
+    fn custom(primary: &Hsl, degrees: &[f64]) -> Vec<ColorVar> {
+        degrees
+            .iter()
+            .filter(|by| by.is_finite())
+            .map(|by| (rotation_name("rot", *by), rotate(primary, *by)))
+            .collect()
+    }
     fn text(primary: &Hsl) -> Vec<ColorVar> {
-        let text_primary = with_saturation(primary, 75.0);
-        let text_primary = with_lightness(&text_primary, 12.5);
-        vec![("--text-primary", text_primary)]
+        let (text_primary, _) = Self::text_and_background(primary);
+        vec![(String::from("--text-primary"), text_primary)]
     }
     fn background(primary: &Hsl) -> Vec<ColorVar> {
-        let background_primary = with_saturation(primary, 25.0);
-        let background_primary = with_lightness(&background_primary, 87.5);
-        vec![("--background-primary", background_primary)]
+        let (_, background_primary) = Self::text_and_background(primary);
+        vec![(String::from("--background-primary"), background_primary)]
+    }
+    /// a dark, saturated text color and a light, desaturated background color,
+    /// pushed apart until they contrast by at least [`TEXT_CONTRAST_TARGET`]
+    ///
+    /// they start out further apart the darker the primary is: from 5% and
+    /// 89% lightness for black to 35% and 59% for white. the background stays
+    /// below 90% so it keeps enough chroma not to count as drifted
+    fn text_and_background(primary: &Hsl) -> (Hsl, Hsl) {
+        let luminance = relative_luminance(primary);
+        let text = with_lightness(&with_saturation(primary, 75.0), 5.0 + 30.0 * luminance);
+        let background = with_lightness(&with_saturation(primary, 25.0), 89.0 - 30.0 * luminance);
+        solve_contrast(&text, &background, TEXT_CONTRAST_TARGET)
+    }
+    fn polyad(primary: &Hsl, n: u8) -> Vec<ColorVar> {
+        let step = Hue::new(360.0 / f64::from(n.max(1)));
+        (1..n)
+            .map(|k| {
+                let by: f64 = (step * f64::from(k)).into();
+                (format!("--polyad-{}", k), rotate(primary, by))
+            })
+            .collect()
+    }
+    fn monochromatic(primary: &Hsl, steps: u8) -> Vec<ColorVar> {
+        (1..=steps)
+            .map(|step| {
+                (
+                    format!("--mono-{}", step),
+                    with_lightness(primary, mono_lightness(step, steps)),
+                )
+            })
+            .collect()
+    }
+    fn perceptual_monochromatic(primary: &Hsl, steps: u8) -> Vec<ColorVar> {
+        (1..=steps)
+            .map(|step| {
+                (
+                    format!("--mono-{}", step),
+                    with_oklab_lightness(primary, mono_lightness(step, steps) / 100.0),
+                )
+            })
+            .collect()
+    }
+    fn perceptual_column(primary: &Hsl) -> Vec<ColorVar> {
+        let lightness = Oklch::from(primary).l;
+        vec![
+            (
+                String::from("--lighter"),
+                with_oklab_lightness(primary, lightness + 0.2),
+            ),
+            (
+                String::from("--darker"),
+                with_oklab_lightness(primary, lightness - 0.2),
+            ),
+        ]
+    }
+    fn pastel(primary: &Hsl) -> Vec<ColorVar> {
+        let pastel = with_saturation(primary, 40.0);
+        let pastel = with_lightness(&pastel, 85.0);
+        vec![(String::from("--pastel-primary"), pastel)]
+    }
+    fn ramp(primary: &Hsl) -> Vec<ColorVar> {
+        let lightness = primary.lightness();
+        vec![
+            (
+                String::from("--tint-1"),
+                with_lightness(primary, lightness + 15.0),
+            ),
+            (
+                String::from("--tint-2"),
+                with_lightness(primary, lightness + 30.0),
+            ),
+            (
+                String::from("--shade-1"),
+                with_lightness(primary, lightness - 15.0),
+            ),
+            (
+                String::from("--shade-2"),
+                with_lightness(primary, lightness - 30.0),
+            ),
+        ]
+    }
+    fn grayscale(primary: &Hsl) -> Vec<ColorVar> {
+        vec![(String::from("--grayscale"), with_saturation(primary, 0.0))]
+    }
+    fn alpha(primary: &Hsl) -> Vec<ColorVar> {
+        [90, 50, 10]
+            .into_iter()
+            .map(|opacity| {
+                let mut color = primary.clone();
+                color.set_alpha(f64::from(opacity) / 100.0);
+                (format!("--primary-{}", opacity), color)
+            })
+            .collect()
+    }
+    fn neutrals(primary: &Hsl) -> Vec<ColorVar> {
+        let neutral = with_saturation(primary, 10.0);
+        vec![
+            (
+                String::from("--neutral-light"),
+                with_lightness(&neutral, 95.0),
+            ),
+            (String::from("--neutral"), with_lightness(&neutral, 50.0)),
+            (
+                String::from("--neutral-dark"),
+                with_lightness(&neutral, 10.0),
+            ),
+        ]
+    }
+}
+
+/// build a [`ColorScheme`] one scheme variant at a time, for schemes that take
+/// parameters
+///
+/// ```
+/// use colorscheme::ColorSchemeBuilder;
+/// use colorsys::Hsl;
+///
+/// let scheme = ColorSchemeBuilder::new()
+///     .primary(Hsl::new(210.0, 50.0, 40.0, None))
+///     .complementary()
+///     .monochromatic(3)
+///     .prefix("brand")
+///     .build()
+///     .unwrap();
+/// assert!(scheme.as_css(None).contains("--brand-mono-3"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorSchemeBuilder {
+    primary: Option<Hsl>,
+    schemes: Vec<Scheme>,
+    direction: Direction,
+    prefix: Option<String>,
+}
+
+impl Default for ColorSchemeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorSchemeBuilder {
+    /// a builder with no primary and no scheme variants
+    pub fn new() -> Self {
+        Self {
+            primary: None,
+            schemes: Vec::new(),
+            direction: Direction::Both,
+            prefix: None,
+        }
+    }
+    /// the primary color, which is required
+    pub fn primary(mut self, primary: Hsl) -> Self {
+        self.primary = Some(primary);
+        self
+    }
+    /// add a scheme variant's colors; as with [`ColorScheme::from_schemes`],
+    /// the first variant to generate a name wins
+    pub fn scheme(mut self, scheme: Scheme) -> Self {
+        self.schemes.push(scheme);
+        self
+    }
+    /// add [`Scheme::Complementary`]
+    pub fn complementary(self) -> Self {
+        self.scheme(Scheme::Complementary)
+    }
+    /// add [`Scheme::Monochromatic`] with `steps` lightnesses
+    pub fn monochromatic(self, steps: u8) -> Self {
+        self.scheme(Scheme::Monochromatic(steps))
+    }
+    /// add [`Scheme::SteppedColumn`], `points` of lightness either side of the primary
+    pub fn column_step(self, points: f64) -> Self {
+        self.scheme(Scheme::SteppedColumn(points))
+    }
+    /// add [`Scheme::Rotated`] by `degrees` clockwise
+    pub fn rotated(self, degrees: f64) -> Self {
+        self.scheme(Scheme::Rotated(degrees))
+    }
+    /// add [`Scheme::Custom`] with a color per rotation in `degrees`
+    pub fn custom(self, degrees: impl IntoIterator<Item = f64>) -> Self {
+        self.scheme(Scheme::from_degrees(degrees))
+    }
+    /// add [`Scheme::Polyad`] of `count` colors
+    pub fn polyad(self, count: u8) -> Self {
+        self.scheme(Scheme::Polyad(count))
+    }
+    /// only emit directional schemes' colors on the `direction` side of the primary
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+    /// see [`ColorScheme::with_prefix`]
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+    /// the colorscheme, or an error if no primary was given
+    pub fn build(self) -> Result<ColorScheme, String> {
+        let primary = self.primary.ok_or("no primary color given")?;
+        let scheme = ColorScheme::from_schemes_in(primary, self.schemes, self.direction);
+        Ok(match &self.prefix {
+            Some(prefix) => scheme.with_prefix(prefix),
+            None => scheme,
+        })
+    }
+}
+
+/// variable names, or prefixes of them, generated by rotating the primary's hue
+const HUE_ROTATION_NAMES: [&str; 14] = [
+    "--clockwise",
+    "--counterclockwise",
+    "--complementary",
+    "--diagonal-complementary",
+    "--upper-right",
+    "--lower-right",
+    "--lower-left",
+    "--rect-",
+    "--analogous-",
+    "--split-complementary-",
+    "--compound-",
+    "--rot-",
+    "--rotated-",
+    "--polyad-",
+];
+
+/// which of [`Order::Canonical`]'s groups the variable `name` belongs to
+fn canonical_group(name: &str) -> u8 {
+    let name = name.strip_suffix("-text").unwrap_or(name);
+    if name.starts_with("--text-") || name.starts_with("--background-") {
+        2
+    } else if HUE_ROTATION_NAMES
+        .iter()
+        .any(|rotation| match rotation.strip_suffix('-') {
+            Some(_) => name.starts_with(rotation),
+            None => name == *rotation,
+        })
+    {
+        1
+    } else {
+        0
     }
 }
 
-fn rotate(color: &Hsl, by: f64) -> Hsl {
+/// clamp `color`'s saturation and lightness to `[0, 100]` and its alpha with
+/// [`clamp_alpha`], for colors entering a scheme
+///
+/// a `NaN` hue, saturation, or lightness becomes `0`, so nothing downstream
+/// writes out garbage
+fn clamp_color(color: Hsl) -> Hsl {
+    let clamp = |val: f64, max: f64| {
+        if val.is_nan() {
+            0.0
+        } else {
+            val.clamp(0.0, max)
+        }
+    };
+    let mut color = clamp_alpha(color);
+    color.set_hue(clamp(color.hue(), 360.0));
+    color.set_saturation(clamp(color.saturation(), 100.0));
+    color.set_lightness(clamp(color.lightness(), 100.0));
+    color
+}
+
+/// clamp `color`'s alpha to `[0, 1]`, treating `NaN` as opaque
+fn clamp_alpha(mut color: Hsl) -> Hsl {
+    let alpha = color.alpha();
+    color.set_alpha(if alpha.is_nan() {
+        1.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    });
+    color
+}
+
+/// `color` rotated `by` degrees clockwise, keeping its saturation, lightness,
+/// and alpha
+///
+/// the hue wraps around the color wheel like [`Hue`] addition, so negative and
+/// multi-turn angles work, and is rounded to hundredths of a degree, so that
+/// chains of rotations don't accumulate floating-point error
+///
+/// ```
+/// use colorscheme::scheme::rotate;
+/// use colorsys::Hsl;
+///
+/// let rotated = rotate(&Hsl::new(300.0, 50.0, 40.0, None), 120.0);
+/// assert_eq!(rotated.hue(), 60.0);
+/// assert_eq!(rotate(&rotated, -120.0).hue(), 300.0);
+/// ```
+pub fn rotate(color: &Hsl, by: f64) -> Hsl {
     let mut c = color.clone();
     let new_hue = Hue::new(color.hue()) + by;
-    c.set_hue(new_hue.into());
+    c.set_hue(hundredths(new_hue.into()));
     c
 }
 
-// not used yet, maybe for e.g. pastelization
-fn with_saturation(color: &Hsl, new_saturation: f64) -> Hsl {
+/// `color` with its saturation replaced, clamped to `[0, 100]`
+///
+/// ```
+/// use colorscheme::scheme::with_saturation;
+/// use colorsys::Hsl;
+///
+/// let muted = with_saturation(&Hsl::new(210.0, 80.0, 40.0, None), 20.0);
+/// assert_eq!(muted.saturation(), 20.0);
+/// assert_eq!(with_saturation(&muted, 150.0).saturation(), 100.0);
+/// ```
+pub fn with_saturation(color: &Hsl, new_saturation: f64) -> Hsl {
     let mut c = color.clone();
-    c.set_saturation(new_saturation);
+    c.set_saturation(new_saturation.clamp(0.0, 100.0));
     c
 }
-fn with_lightness(color: &Hsl, new_lightness: f64) -> Hsl {
+/// `color` with its lightness replaced, clamped to `[0, 100]`
+///
+/// ```
+/// use colorscheme::scheme::{rotate, with_lightness};
+/// use colorsys::Hsl;
+///
+/// let primary = Hsl::new(210.0, 50.0, 40.0, None);
+/// let darker = with_lightness(&rotate(&primary, 120.0), primary.lightness() - 15.0);
+/// assert_eq!((darker.hue(), darker.lightness()), (330.0, 25.0));
+/// assert_eq!(with_lightness(&darker, -10.0).lightness(), 0.0);
+/// ```
+pub fn with_lightness(color: &Hsl, new_lightness: f64) -> Hsl {
     let mut c = color.clone();
-    c.set_lightness(new_lightness);
+    c.set_lightness(new_lightness.clamp(0.0, 100.0));
     c
 }
 
-fn invert(val: f64) -> f64 {
-    if val > 100.0 {
-        0.0
-    } else if val < 0.0 {
-        100.0
-    } else {
-        100.0 - val
+/// `color` at Oklab lightness `lightness`, clamped to `[0, 1]`, keeping its Oklab
+/// hue and as much of its chroma as fits in sRGB
+fn with_oklab_lightness(color: &Hsl, lightness: f64) -> Hsl {
+    let oklch = Oklch {
+        l: lightness.clamp(0.0, 1.0),
+        ..Oklch::from(color)
+    };
+    let mut c = Hsl::from(oklch.to_rgb_clipped());
+    if color.alpha() < 1.0 {
+        c.set_alpha(color.alpha());
     }
-}
-fn hsl_to_css(h: &Hsl) -> String {
-    colorsys::Rgb::from(h).to_hex_string()
+    c
 }
 
-#[cfg(test)]
+/// the HSL lightness, in percent, of step `step` of `steps` in a monochromatic scheme
+fn mono_lightness(step: u8, steps: u8) -> f64 {
+    if steps == 1 {
+        50.0
+    } else {
+        10.0 + 80.0 * f64::from(step - 1) / f64::from(steps - 1)
+    }
+}
+
+/// how colorful an HSL color actually looks, as a percentage
+///
+/// saturation is scaled down the closer lightness gets to black or white, where
+/// every hue converges
+fn effective_chroma(color: &Hsl) -> f64 {
+    (1.0 - (color.lightness() / 50.0 - 1.0).abs()) * color.saturation()
+}
+
+/// whether a saturated color's lightness is so extreme that its saturation no
+/// longer means anything, i.e. it has lost its hue identity
+///
+/// deliberately unsaturated colors (e.g. [`Scheme::Neutrals`]) never count as drifted
+fn saturation_drifted(color: &Hsl) -> bool {
+    color.saturation() >= DRIFT_MIN_SATURATION && effective_chroma(color) < DRIFT_CHROMA
+}
+
+/// the whitespace CSS is written out with
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    /// before every line but the first
+    newline: &'static str,
+    /// per level of nesting
+    indent: &'static str,
+    /// after a `:` and before a `{`
+    space: &'static str,
+}
+
+impl Layout {
+    const PRETTY: Layout = Layout {
+        newline: "\n",
+        indent: "\t",
+        space: " ",
+    };
+    const MINIFIED: Layout = Layout {
+        newline: "",
+        indent: "",
+        space: "",
+    };
+    fn is_minified(&self) -> bool {
+        self.newline.is_empty()
+    }
+    /// `value`, without the optional spaces inside color functions when minified
+    ///
+    /// the spaces between `oklch()`'s components are required, so they stay
+    fn value(&self, value: &str) -> String {
+        if self.is_minified() {
+            value.replace(", ", ",").replace(" / ", "/")
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// a `selector { --name: value; ... }` block nested `depth` levels deep, with each
+/// declaration's optional comment after it (unless minified)
+fn write_block(
+    s: &mut String,
+    layout: Layout,
+    depth: usize,
+    selector: &str,
+    declarations: Vec<(String, String, Option<&str>)>,
+) {
+    let indent = layout.indent.repeat(depth);
+    write!(s, "{}{}{}{{", indent, selector, layout.space).unwrap();
+    let count = declarations.len();
+    for (i, (var_name, value, comment)) in declarations.into_iter().enumerate() {
+        let value = layout.value(&value);
+        let (newline, space) = (layout.newline, layout.space);
+        write!(
+            s,
+            "{newline}{indent}{}{var_name}:{space}{value}",
+            layout.indent
+        )
+        .unwrap();
+        if layout.is_minified() {
+            // the last declaration in a block needs no `;`
+            if i + 1 < count {
+                s.push(';');
+            }
+        } else {
+            s.push(';');
+            if let Some(comment) = comment {
+                write!(s, " /* {} */", comment).unwrap();
+            }
+        }
+    }
+    write!(s, "{}{}}}", layout.newline, indent).unwrap();
+}
+
+/// an `@media (<feature>: <value>)` block with `entries` under `selector`
+fn write_media_block(
+    s: &mut String,
+    layout: Layout,
+    (feature, value): (&str, &str),
+    selector: &str,
+    entries: Vec<(String, String)>,
+) {
+    let space = layout.space;
+    write!(
+        s,
+        "{}@media ({feature}:{space}{value}){space}{{",
+        layout.newline
+    )
+    .unwrap();
+    s.push_str(layout.newline);
+    let declarations = entries
+        .into_iter()
+        .map(|(var_name, value)| (var_name, value, None))
+        .collect();
+    write_block(s, layout, 1, selector, declarations);
+    write!(s, "{}}}", layout.newline).unwrap();
+}
+
+/// `selector` trimmed, or `:root` if it's missing, blank, or contains a brace
+fn css_selector(selector: Option<&str>) -> &str {
+    match selector.map(str::trim) {
+        Some(selector) if !selector.is_empty() && !selector.contains(['{', '}']) => selector,
+        _ => ":root",
+    }
+}
+
+/// whether `name` matches the CSS custom property grammar
+fn is_custom_property_name(name: &str) -> bool {
+    match name.strip_prefix("--") {
+        Some(rest) => {
+            !rest.is_empty()
+                && rest
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii())
+        }
+        None => false,
+    }
+}
+
+/// collapse colors that are near-duplicates of an earlier color, keeping the
+/// first of each group
+///
+/// colors count as near-duplicates when their hues are within `tolerance_deg`
+/// of each other (accounting for wraparound) and their saturation and lightness
+/// are each within 5 percentage points
+pub fn dedup_hues(colors: &mut Vec<Hsl>, tolerance_deg: f64) {
+    let mut kept: Vec<Hsl> = Vec::with_capacity(colors.len());
+    for color in colors.drain(..) {
+        let duplicate = kept.iter().any(|k| {
+            Hue::new(k.hue()).distance(Hue::new(color.hue())) <= tolerance_deg
+                && (k.saturation() - color.saturation()).abs() <= DEDUP_SL_TOLERANCE
+                && (k.lightness() - color.lightness()).abs() <= DEDUP_SL_TOLERANCE
+        });
+        if !duplicate {
+            kept.push(color);
+        }
+    }
+    *colors = kept;
+}
+
+/// iterate over [`ColorScheme::variables`]
+impl<'a> IntoIterator for &'a ColorScheme {
+    type Item = (&'a str, &'a Hsl);
+    type IntoIter = Variables<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.variables()
+    }
+}
+
+/// the scheme as CSS under `:root`, see [`ColorScheme::as_css`]
+impl std::fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_css(None))
+    }
+}
+
+/// append the colors in `new` whose names aren't in `colors` yet
+fn extend_unique(colors: &mut Vec<ColorVar>, new: Vec<ColorVar>) {
+    for (name, color) in new {
+        if !colors.iter().any(|(existing, _)| *existing == name) {
+            colors.push((name, color));
+        }
+    }
+}
+
+/// `s` with HTML's special characters escaped
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `s` as a quoted JSON string
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// `name` with a leading `--`, adding one if it's missing
+fn var_name(name: &str) -> String {
+    if name.starts_with("--") {
+        name.to_string()
+    } else {
+        format!("--{}", name)
+    }
+}
+
+/// `--rot-<degrees>`, normalized to `[0, 360)` with `.` replaced by `_` so the
+/// name stays a valid CSS identifier
+fn rotation_name(prefix: &str, degrees: f64) -> String {
+    // wrap first so huge angles can't overflow when scaled, then round to
+    // hundredths and wrap again, so a hair under 360 becomes 0; adding 0.0
+    // turns -0.0 into 0.0
+    let hundredths = (degrees.rem_euclid(360.0) * 100.0).round();
+    let normalized = (hundredths.rem_euclid(36_000.0) + 0.0) / 100.0;
+    format!("--{}-{}", prefix, normalized).replace('.', "_")
+}
+
+/// the color `t` of the way from `from` to `to`, taking the shorter way around the hue circle
+fn lerp(from: &Hsl, to: &Hsl, t: f64) -> Hsl {
+    let mix = |a: f64, b: f64| a + (b - a) * t;
+    Hsl::new(
+        Hue::new(from.hue()).lerp(Hue::new(to.hue()), t).into(),
+        mix(from.saturation(), to.saturation()),
+        mix(from.lightness(), to.lightness()),
+        Some(mix(from.alpha(), to.alpha())),
+    )
+}
+
+/// the rotation from hue `a` to hue `b` along the shortest arc, from `-180.0` to `180.0`
+fn signed_rotation(a: f64, b: f64) -> f64 {
+    let d = (b - a).rem_euclid(360.0);
+    if d > 180.0 { d - 360.0 } else { d }
+}
+
+fn tenths(val: f64) -> f64 {
+    (val * 10.0).round() / 10.0
+}
+
+fn hundredths(val: f64) -> f64 {
+    (val * 100.0).round() / 100.0
+}
+
+fn invert(val: f64) -> f64 {
+    if val > 100.0 {
+        0.0
+    } else if val < 0.0 {
+        100.0
+    } else {
+        100.0 - val
+    }
+}
+/// 6-digit hex for opaque colors, 8-digit hex for translucent ones
+fn hsl_to_css(h: &Hsl) -> String {
+    rgb_to_hex(&colorsys::Rgb::from(h))
+}
+fn rgb_to_hex(rgb: &colorsys::Rgb) -> String {
+    let hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        round_channel(rgb.red()),
+        round_channel(rgb.green()),
+        round_channel(rgb.blue())
+    );
+    if rgb.alpha() < 1.0 {
+        format!("{}{:02x}", hex, round_channel(rgb.alpha() * 255.0))
+    } else {
+        hex
+    }
+}
+/// round a `0.0`-`255.0` channel to the nearest integer
+///
+/// floating-point noise is snapped away first, so a channel landing a hair
+/// either side of `.5` (which can differ across platforms) always rounds the same way
+fn round_channel(channel: f64) -> u8 {
+    ((channel * 1e6).round() / 1e6).round() as u8
+}
+/// write a single color out in `format`
+pub fn format_color(color: &Hsl, format: Format) -> String {
+    serialize(&Color::from(color), format, false)
+}
+
+/// a notice if writing `color` out in `format` loses precision, e.g. a hue
+/// with several decimal places written as hex
+///
+/// only [`Format::Hex`], [`Format::Rgb`], and [`Format::Hsl`] are checked
+pub fn precision_loss(color: &Hsl, format: Format) -> Option<String> {
+    let written = match format {
+        Format::Hex | Format::Rgb => {
+            let rgb = colorsys::Rgb::from(color);
+            let [r, g, b] = [rgb.red(), rgb.green(), rgb.blue()].map(round_channel);
+            Hsl::from(colorsys::Rgb::new(r.into(), g.into(), b.into(), None))
+        }
+        Format::Hsl => Hsl::new(
+            (color.hue() * 10.0).round() / 10.0,
+            color.saturation().round(),
+            color.lightness().round(),
+            None,
+        ),
+        Format::Oklch => return None,
+    };
+    let tolerance = 0.01;
+    // the hue of a gray is meaningless
+    let hue_lost = color.saturation() > 0.0
+        && color.lightness() > 0.0
+        && color.lightness() < 100.0
+        && Hue::new(color.hue()).distance(Hue::new(written.hue())) > tolerance;
+    let lost = hue_lost
+        || (color.saturation() - written.saturation()).abs() > tolerance
+        || (color.lightness() - written.lightness()).abs() > tolerance;
+    lost.then(|| {
+        format!(
+            "hsl({}, {}%, {}%) can't be written exactly as {}",
+            color.hue(),
+            color.saturation(),
+            color.lightness(),
+            format_color(color, format)
+        )
+    })
+}
+
+fn serialize(color: &Color, format: Format, keep_360: bool) -> String {
+    match format {
+        Format::Hex => rgb_to_hex(color.rgb()),
+        Format::Rgb => rgb_to_rgb_css(color.rgb()),
+        Format::Hsl => hsl_to_hsl_css(color.hsl(), keep_360),
+        Format::Oklch => hsl_to_oklch_css(color.hsl()),
+    }
+}
+
+/// `rgb()` with whole-number channels
+fn rgb_to_rgb_css(rgb: &colorsys::Rgb) -> String {
+    let [r, g, b] = [rgb.red(), rgb.green(), rgb.blue()].map(round_channel);
+    if rgb.alpha() < 1.0 {
+        format!(
+            "rgba({}, {}, {}, {})",
+            r,
+            g,
+            b,
+            (rgb.alpha() * 100.0).round() / 100.0
+        )
+    } else {
+        format!("rgb({}, {}, {})", r, g, b)
+    }
+}
+/// `hsl()` with the hue rounded to a tenth of a degree and whole-percent
+/// saturation and lightness
+///
+/// `360` is emitted as the equivalent `0` unless `keep_360` is set
+fn hsl_to_hsl_css(h: &Hsl, keep_360: bool) -> String {
+    let hue = (h.hue() * 10.0).round() / 10.0;
+    let hue = if hue == 360.0 && !keep_360 { 0.0 } else { hue };
+    let saturation = h.saturation().round();
+    let lightness = h.lightness().round();
+    if h.alpha() < 1.0 {
+        format!(
+            "hsla({}, {}%, {}%, {})",
+            hue,
+            saturation,
+            lightness,
+            (h.alpha() * 100.0).round() / 100.0
+        )
+    } else {
+        format!("hsl({}, {}%, {}%)", hue, saturation, lightness)
+    }
+}
+/// `oklch()` with lightness to a tenth of a percent, chroma to three decimal
+/// places, and hue to two
+///
+/// colors whose chroma rounds to `0` have no meaningful hue, so it's written as `0`
+fn hsl_to_oklch_css(h: &Hsl) -> String {
+    let oklch = Oklch::from(h);
+    let lightness = (oklch.l * 1000.0).round() / 10.0;
+    let chroma = (oklch.c * 1000.0).round() / 1000.0;
+    let hue = if chroma == 0.0 {
+        0.0
+    } else {
+        (oklch.h * 100.0).round() / 100.0
+    };
+    if h.alpha() < 1.0 {
+        format!(
+            "oklch({}% {} {} / {})",
+            lightness,
+            chroma,
+            hue,
+            (h.alpha() * 100.0).round() / 100.0
+        )
+    } else {
+        format!("oklch({}% {} {})", lightness, chroma, hue)
+    }
+}
+
+/// serde support, behind the `serde` feature
+///
+/// a [`ColorScheme`] serializes as its primary and `(name, color)` pairs, with
+/// colors as their `h`, `s`, `l`, and `a` components; output settings such as
+/// the [`Format`] aren't kept, so a deserialized scheme has the defaults. a
+/// [`Scheme`] serializes as its [`Scheme::name`], so schemes with parameters,
+/// such as [`Scheme::Custom`], can't be serialized
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{ColorScheme, Scheme};
+    use colorsys::{ColorAlpha, Hsl};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+
+    #[derive(Serialize, Deserialize)]
+    struct Components {
+        h: f64,
+        s: f64,
+        l: f64,
+        a: f64,
+    }
+
+    impl From<&Hsl> for Components {
+        fn from(hsl: &Hsl) -> Self {
+            Components {
+                h: hsl.hue(),
+                s: hsl.saturation(),
+                l: hsl.lightness(),
+                a: hsl.alpha(),
+            }
+        }
+    }
+
+    impl From<Components> for Hsl {
+        fn from(c: Components) -> Self {
+            Hsl::new(c.h, c.s, c.l, Some(c.a))
+        }
+    }
+
+    #[derive(Serialize)]
+    struct SerializeScheme<'a> {
+        primary: Components,
+        colors: Vec<(&'a str, Components)>,
+    }
+
+    #[derive(Deserialize)]
+    struct DeserializeScheme {
+        primary: Components,
+        colors: Vec<(String, Components)>,
+    }
+
+    impl Serialize for ColorScheme {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            SerializeScheme {
+                primary: Components::from(&self.primary),
+                colors: self
+                    .colors
+                    .iter()
+                    .map(|(name, color)| (name.as_str(), Components::from(color)))
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ColorScheme {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let scheme = DeserializeScheme::deserialize(deserializer)?;
+            Ok(ColorScheme {
+                colors: scheme
+                    .colors
+                    .into_iter()
+                    .map(|(name, color)| (name, Hsl::from(color)))
+                    .collect(),
+                ..ColorScheme::from_schemes(Hsl::from(scheme.primary), [])
+            })
+        }
+    }
+
+    impl Serialize for Scheme {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let name = self.name().ok_or_else(|| {
+                ser::Error::custom(format!("{:?} has no name to serialize as", self))
+            })?;
+            serializer.serialize_str(name)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Scheme {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::contrast::contrast_ratio;
     fn _new_hsl(h: f64) -> Hsl {
         Hsl::new(h, 100.0, 50.0, Some(1.0))
     }
+    /// `colors` with their colors as hex, to compare them without tripping on
+    /// floating-point differences that don't change the output
+    fn _hex(colors: &[ColorVar]) -> Vec<(&str, String)> {
+        colors
+            .iter()
+            .map(|(name, color)| (name.as_str(), hsl_to_css(color)))
+            .collect()
+    }
     #[test]
     fn test_rotate() {
         let o = _new_hsl(270.0);
@@ -186,21 +2103,46 @@ mod tests {
         assert_eq!(77.5, invert(22.5));
     }
     #[test]
+    fn test_column_clamps_lightness() {
+        let primary = Hsl::new(200.0, 50.0, 80.0, None);
+        let column = ColorScheme::column(&primary);
+        assert_eq!(column[0].0, "--lighter");
+        assert_eq!(column[0].1.lightness(), 100.0);
+        assert_eq!(column[1].1.lightness(), 40.0);
+        assert_eq!(with_lightness(&primary, -10.0).lightness(), 0.0);
+        assert_eq!(with_saturation(&primary, 150.0).saturation(), 100.0);
+    }
+    #[test]
+    fn test_stepped_column() {
+        let light = Hsl::new(200.0, 50.0, 80.0, None);
+        let column = ColorScheme::stepped_column(&light, 15.0);
+        assert_eq!(column[0].0, "--lighter");
+        assert_eq!(column[0].1.lightness(), 95.0);
+        assert_eq!(column[1].1.lightness(), 65.0);
+        let dark = Hsl::new(200.0, 50.0, 10.0, None);
+        let column = ColorScheme::stepped_column(&dark, 15.0);
+        assert_eq!(column[0].1.lightness(), 25.0);
+        assert_eq!(column[1].1.lightness(), 0.0);
+    }
+    #[test]
     fn test_complementary() {
         let primary: f64 = 90.0;
         let expected_complementary: f64 = 270.0;
-        let expected: Vec<ColorVar> = vec![("--complementary", _new_hsl(expected_complementary))];
+        let expected: Vec<ColorVar> = vec![(
+            String::from("--complementary"),
+            _new_hsl(expected_complementary),
+        )];
         let complementary = ColorScheme::complementary(&_new_hsl(primary));
-        assert_eq!(complementary, expected);
+        assert_eq!(_hex(&complementary), _hex(&expected));
     }
     #[test]
     fn test_diagonal_complementary() {
         let primary_hue: f64 = 90.0;
         let primary = Hsl::new(primary_hue, 20.0, 30.0, Some(1.0));
         let expected_hsl = Hsl::new(270.0, 80.0, 70.0, Some(1.0));
-        let expected = vec![("--diagonal-complementary", expected_hsl)];
+        let expected = vec![(String::from("--diagonal-complementary"), expected_hsl)];
         let actual = ColorScheme::diagonal_complementary(&primary);
-        assert_eq!(actual, expected);
+        assert_eq!(_hex(&actual), _hex(&expected));
     }
     #[test]
     fn test_triad() {
@@ -208,11 +2150,53 @@ mod tests {
         let expected_clockwise: f64 = 210.0;
         let expected_counterclockwise: f64 = 330.0;
         let expected: Vec<ColorVar> = vec![
-            ("--clockwise", _new_hsl(expected_clockwise)),
-            ("--counterclockwise", _new_hsl(expected_counterclockwise)),
+            (String::from("--clockwise"), _new_hsl(expected_clockwise)),
+            (
+                String::from("--counterclockwise"),
+                _new_hsl(expected_counterclockwise),
+            ),
         ];
         let triad = ColorScheme::triad(&_new_hsl(primary));
-        assert_eq!(triad, expected);
+        assert_eq!(_hex(&triad), _hex(&expected));
+    }
+    #[test]
+    fn test_analogous() {
+        let expected: Vec<ColorVar> = vec![
+            (String::from("--analogous-clockwise"), _new_hsl(120.0)),
+            (String::from("--analogous-counterclockwise"), _new_hsl(60.0)),
+        ];
+        let analogous = ColorScheme::analogous(&_new_hsl(90.0));
+        assert_eq!(_hex(&analogous), _hex(&expected));
+    }
+    #[test]
+    fn test_compound() {
+        let expected: Vec<ColorVar> = vec![
+            (String::from("--complementary"), _new_hsl(180.0)),
+            (String::from("--compound-clockwise"), _new_hsl(150.0)),
+            (String::from("--compound-counterclockwise"), _new_hsl(210.0)),
+        ];
+        assert_eq!(
+            _hex(&ColorScheme::compound(&_new_hsl(0.0))),
+            _hex(&expected)
+        );
+    }
+    #[test]
+    fn test_split_complementary() {
+        let expected: Vec<ColorVar> = vec![
+            (
+                String::from("--split-complementary-clockwise"),
+                _new_hsl(150.0),
+            ),
+            (
+                String::from("--split-complementary-counterclockwise"),
+                _new_hsl(210.0),
+            ),
+        ];
+        let split = ColorScheme::split_complementary(&_new_hsl(0.0));
+        assert_eq!(_hex(&split), _hex(&expected));
+        let wrapped = ColorScheme::split_complementary(&_new_hsl(300.0));
+        assert_eq!(wrapped[0].1.hue(), 90.0);
+        assert_eq!(wrapped[1].1.hue(), 150.0);
     }
     #[test]
     fn test_tetrad() {
@@ -221,30 +2205,51 @@ mod tests {
         let expected_lower_right = 270.0;
         let expected_lower_left = 360.0;
         let expected = vec![
-            ("--upper-right", _new_hsl(expected_upper_right)),
-            ("--lower-right", _new_hsl(expected_lower_right)),
-            ("--lower-left", _new_hsl(expected_lower_left)),
+            (
+                String::from("--upper-right"),
+                _new_hsl(expected_upper_right),
+            ),
+            (
+                String::from("--lower-right"),
+                _new_hsl(expected_lower_right),
+            ),
+            (String::from("--lower-left"), _new_hsl(expected_lower_left)),
         ];
         let tetrad = ColorScheme::tetrad(&_new_hsl(primary));
-        assert_eq!(tetrad, expected);
+        assert_eq!(_hex(&tetrad), _hex(&expected));
+    }
+    #[test]
+    fn test_rectangle() {
+        let expected = vec![
+            (String::from("--rect-upper-right"), _new_hsl(60.0)),
+            (String::from("--rect-lower-right"), _new_hsl(180.0)),
+            (String::from("--rect-lower-left"), _new_hsl(240.0)),
+        ];
+        let rectangle = ColorScheme::rectangle(&_new_hsl(0.0));
+        assert_eq!(_hex(&rectangle), _hex(&expected));
+        assert_eq!("rectangle".parse(), Ok(Scheme::Rectangle));
+        let clockwise =
+            ColorScheme::from_schemes_in(_new_hsl(0.0), [Scheme::Rectangle], Direction::Clockwise);
+        assert!(clockwise.get("--rect-upper-right").is_some());
+        assert!(clockwise.get("--rect-lower-left").is_none());
     }
     #[test]
     fn test_text() {
         let primary_hue: f64 = 90.0;
-        let primary = Hsl::new(primary_hue, 50.0, 50.0, Some(1.0));
-        let expected_hsl = Hsl::new(primary_hue, 75.0, 12.5, Some(1.0));
-        let expected = vec![("--text-primary", expected_hsl)];
+        let primary = Hsl::new(primary_hue, 50.0, 0.0, Some(1.0));
+        let expected_hsl = Hsl::new(primary_hue, 75.0, 5.0, Some(1.0));
+        let expected = vec![(String::from("--text-primary"), expected_hsl)];
         let actual = ColorScheme::text(&primary);
-        assert_eq!(actual, expected);
+        assert_eq!(_hex(&actual), _hex(&expected));
     }
     #[test]
     fn test_background() {
         let primary_hue: f64 = 90.0;
-        let primary = Hsl::new(primary_hue, 50.0, 50.0, Some(1.0));
-        let expected_hsl = Hsl::new(primary_hue, 25.0, 87.5, Some(1.0));
-        let expected = vec![("--background-primary", expected_hsl)];
+        let primary = Hsl::new(primary_hue, 50.0, 0.0, Some(1.0));
+        let expected_hsl = Hsl::new(primary_hue, 25.0, 89.0, Some(1.0));
+        let expected = vec![(String::from("--background-primary"), expected_hsl)];
         let actual = ColorScheme::background(&primary);
-        assert_eq!(actual, expected);
+        assert_eq!(_hex(&actual), _hex(&expected));
     }
     #[test]
     fn test_hsl_to_css() {
@@ -253,12 +2258,1233 @@ mod tests {
         assert_eq!(hsl_to_css(&red), expected);
     }
     #[test]
+    fn test_hsl_to_css_alpha() {
+        let translucent = Hsl::new(0.0, 100.0, 50.0, Some(0.5));
+        assert_eq!(hsl_to_css(&translucent), String::from("#ff000080"));
+        let opaque = Hsl::new(0.0, 100.0, 50.0, None);
+        assert_eq!(hsl_to_css(&opaque), String::from("#ff0000"));
+    }
+    #[test]
     fn test_as_css() {
         let primary = _new_hsl(0.0);
         let expected =
-            String::from(":root {\n\t--primary: #ff0000;\n\t--complementary: #00ffff;\n};");
+            String::from(":root {\n\t--primary: #ff0000;\n\t--complementary: #00ffff;\n}");
         let dyad = ColorScheme::new(primary, Scheme::Complementary);
         let actual = dyad.as_css(None);
         assert_eq!(actual, expected);
     }
+    #[test]
+    fn test_keyword_override() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_override("complementary", Override::keyword("currentcolor").unwrap());
+        let actual = scheme.as_css(None);
+        assert!(actual.contains("\n\t--complementary: currentColor;"));
+        assert!(actual.contains("\n\t--primary: #ff0000;"));
+    }
+    #[test]
+    fn test_color_override() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_override("--primary", Override::Color(_new_hsl(240.0)));
+        assert!(scheme.as_css(None).contains("\n\t--primary: #0000ff;"));
+    }
+    #[test]
+    fn test_keyword() {
+        assert_eq!(
+            Override::keyword("INHERIT"),
+            Some(Override::Keyword(String::from("inherit")))
+        );
+        assert_eq!(Override::keyword("red"), None);
+    }
+    #[test]
+    fn test_without_primary() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).without_primary();
+        let names: Vec<String> = scheme.entries().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["--complementary"]);
+        let expected = String::from(".theme {\n\t--complementary: #00ffff;\n}");
+        assert_eq!(scheme.as_css(Some(".theme")), expected);
+    }
+    #[test]
+    fn test_max_delta() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        assert_eq!(scheme.max_delta(&scheme.clone()), 0.0);
+        let shifted = ColorScheme::new(_new_hsl(10.0), Scheme::Triad);
+        assert!(scheme.max_delta(&shifted) > 0.0);
+    }
+    #[test]
+    fn test_with_text_pairs() {
+        let scheme = ColorScheme::new(_new_hsl(60.0), Scheme::Triad).with_text_pairs();
+        let names = |scheme: &ColorScheme| -> Vec<String> {
+            scheme.entries().into_iter().map(|(n, _)| n).collect()
+        };
+        assert_eq!(
+            names(&scheme),
+            vec![
+                "--primary",
+                "--primary-text",
+                "--clockwise",
+                "--clockwise-text",
+                "--counterclockwise",
+                "--counterclockwise-text"
+            ]
+        );
+        assert_eq!(scheme.get("--primary-text"), None);
+        let without_primary = ColorScheme::new(_new_hsl(60.0), Scheme::Triad)
+            .with_text_pairs()
+            .without_primary();
+        assert_eq!(
+            names(&without_primary),
+            vec![
+                "--clockwise",
+                "--clockwise-text",
+                "--counterclockwise",
+                "--counterclockwise-text"
+            ]
+        );
+        let added_later = ColorScheme::new(_new_hsl(60.0), Scheme::Complementary)
+            .with_text_pairs()
+            .and(Scheme::Column)
+            .merge(ColorScheme::new(_new_hsl(60.0), Scheme::Grayscale));
+        assert_eq!(
+            names(&added_later),
+            vec![
+                "--primary",
+                "--primary-text",
+                "--complementary",
+                "--complementary-text",
+                "--lighter",
+                "--lighter-text",
+                "--darker",
+                "--darker-text",
+                "--grayscale",
+                "--grayscale-text"
+            ]
+        );
+        let black = Hsl::new(0.0, 0.0, 0.0, None);
+        let white = Hsl::new(0.0, 0.0, 100.0, None);
+        let vars = scheme.ordered_variables();
+        for pair in vars.chunks(2) {
+            let [(name, color), (text_name, text)] = pair else {
+                unreachable!()
+            };
+            assert_eq!(*text_name, format!("{}-text", name));
+            let (color, text) = (color.hsl(), text.hsl());
+            let best = contrast_ratio(color, &black).max(contrast_ratio(color, &white));
+            assert_eq!(contrast_ratio(color, text), best);
+        }
+    }
+    #[test]
+    fn test_hsl_to_hsl_css() {
+        assert_eq!(hsl_to_hsl_css(&_new_hsl(0.0), false), "hsl(0, 100%, 50%)");
+        let translucent = Hsl::new(123.456, 50.4, 39.6, Some(0.5));
+        assert_eq!(
+            hsl_to_hsl_css(&translucent, false),
+            "hsla(123.5, 50%, 40%, 0.5)"
+        );
+    }
+    #[test]
+    fn test_hsl_to_oklch_css() {
+        assert_eq!(
+            format_color(&_new_hsl(0.0), Format::Oklch),
+            "oklch(62.8% 0.258 29.23)"
+        );
+        let translucent = Hsl::new(0.0, 100.0, 50.0, Some(0.5));
+        assert_eq!(
+            format_color(&translucent, Format::Oklch),
+            "oklch(62.8% 0.258 29.23 / 0.5)"
+        );
+        let white = Hsl::new(0.0, 0.0, 100.0, None);
+        assert_eq!(format_color(&white, Format::Oklch), "oklch(100% 0 0)");
+        let gray = ColorScheme::new(_new_hsl(200.0), Scheme::Grayscale)
+            .with_format(Format::Oklch)
+            .as_css(None);
+        assert!(gray.contains("\n\t--grayscale: oklch(59.8% 0 0);"));
+    }
+    #[test]
+    fn test_hsl_normalizes_360() {
+        // 90 + 3 * 90 lands exactly on 360
+        let scheme = ColorScheme::new(_new_hsl(90.0), Scheme::Tetrad).with_format(Format::Hsl);
+        assert!(
+            scheme
+                .as_css(None)
+                .contains("\n\t--lower-left: hsl(0, 100%, 50%);")
+        );
+    }
+    #[test]
+    fn test_hsl_keeping_360() {
+        let scheme = ColorScheme::new(_new_hsl(90.0), Scheme::Tetrad)
+            .with_format(Format::Hsl)
+            .keeping_360();
+        assert!(
+            scheme
+                .as_css(None)
+                .contains("\n\t--lower-left: hsl(360, 100%, 50%);")
+        );
+    }
+    #[test]
+    fn test_harmony_score() {
+        let triad = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        assert_eq!(triad.harmony_score(), 1.0);
+        let scatter = ColorScheme {
+            colors: vec![
+                (String::from("--a"), _new_hsl(47.0)),
+                (String::from("--b"), _new_hsl(101.0)),
+                (String::from("--c"), _new_hsl(163.0)),
+            ],
+            ..triad.clone()
+        };
+        assert!(triad.harmony_score() > scatter.harmony_score());
+        let split = ColorScheme::from_schemes(_new_hsl(0.0), [Scheme::Custom(vec![150.0])]);
+        assert_eq!(split.harmony_score(), 1.0);
+        let worst = ColorScheme::from_schemes(_new_hsl(0.0), [Scheme::Custom(vec![165.0])]);
+        assert_eq!(worst.harmony_score(), 0.0);
+    }
+    #[test]
+    fn test_saturation_drift() {
+        let primary = Hsl::new(200.0, 50.0, 50.0, None);
+        assert!(!saturation_drifted(&primary));
+        assert!(saturation_drifted(&with_lightness(&primary, 99.0)));
+        let gray = Hsl::new(0.0, 0.0, 99.0, None);
+        assert!(!saturation_drifted(&gray));
+        // colors below `DRIFT_MIN_SATURATION` are muted on purpose, like neutrals
+        assert!(!saturation_drifted(&Hsl::new(200.0, 19.9, 99.0, None)));
+        assert!(saturation_drifted(&Hsl::new(200.0, 20.0, 99.0, None)));
+        let neutrals = ColorScheme::new(Hsl::new(200.0, 80.0, 50.0, None), Scheme::Neutrals);
+        assert!(neutrals.warnings().is_empty());
+    }
+    #[test]
+    fn test_warnings() {
+        let primary = Hsl::new(200.0, 50.0, 80.0, None);
+        let scheme = ColorScheme::new(primary, Scheme::Column);
+        let warnings = scheme.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("`--lighter`"));
+    }
+    #[test]
+    fn test_get() {
+        let scheme = ColorScheme::new(_new_hsl(90.0), Scheme::Complementary);
+        assert_eq!(scheme.get("--complementary"), Some(&_new_hsl(270.0)));
+        assert_eq!(scheme.get("--primary"), Some(&_new_hsl(90.0)));
+        assert_eq!(scheme.get("--nope"), None);
+    }
+    #[test]
+    fn test_high_contrast() {
+        let primary = Hsl::new(200.0, 50.0, 50.0, None);
+        let scheme = ColorScheme::from_schemes(primary, [Scheme::Text, Scheme::Background])
+            .with_high_contrast();
+        let css = scheme.as_css(None);
+        assert!(
+            css.contains("\n@media (prefers-contrast: more) {\n\t:root {\n\t\t--text-primary: ")
+        );
+        let base = contrast_ratio(
+            scheme.get("--text-primary").unwrap(),
+            scheme.get("--background-primary").unwrap(),
+        );
+        let colors = scheme.high_contrast_colors().unwrap();
+        let high = contrast_ratio(&colors[0].1, &colors[1].1);
+        assert!(high > base);
+    }
+    #[test]
+    fn test_high_contrast_without_text() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).with_high_contrast();
+        assert!(!scheme.as_css(None).contains("@media"));
+    }
+    #[test]
+    fn test_custom() {
+        let primary = _new_hsl(90.0);
+        let expected: Vec<ColorVar> = vec![
+            (String::from("--rot-30"), _new_hsl(120.0)),
+            (String::from("--rot-210"), _new_hsl(300.0)),
+        ];
+        let custom = ColorScheme::colors(
+            &primary,
+            Scheme::from_degrees([30.0, 210.0]),
+            Direction::Both,
+        );
+        assert_eq!(_hex(&custom), _hex(&expected));
+    }
+    #[test]
+    fn test_rotation_name() {
+        assert_eq!(rotation_name("rot", -30.0), "--rot-330");
+        assert_eq!(rotation_name("rot", 22.5), "--rot-22_5");
+        assert_eq!(rotation_name("rot", -0.001), "--rot-0");
+        assert_eq!(rotation_name("rotated", 359.999), "--rotated-0");
+        assert_eq!(rotation_name("rot", -30.33), "--rot-329_67");
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::from_degrees([1e308, f64::NAN]));
+        assert_eq!(scheme.variables().count(), 2);
+        let (name, color) = scheme.variables().nth(1).unwrap();
+        assert!(!name.contains("NaN"));
+        assert_eq!(name, rotation_name("rot", color.hue()));
+    }
+    #[test]
+    fn test_round_channel() {
+        assert_eq!(round_channel(127.5), 128);
+        assert_eq!(round_channel(127.49999999999), 128);
+        assert_eq!(round_channel(127.4), 127);
+    }
+    #[test]
+    fn test_pinned_hex() {
+        // guards against output drifting across platforms
+        let cases = [
+            (Hsl::new(0.0, 100.0, 25.0, None), Scheme::Complementary),
+            (Hsl::new(37.0, 83.0, 61.0, None), Scheme::Triad),
+            (Hsl::new(210.0, 50.0, 40.0, None), Scheme::Tetrad),
+            (Hsl::new(300.0, 20.0, 85.0, None), Scheme::Column),
+        ];
+        let expected = [
+            vec!["#800000", "#008080"],
+            vec!["#eeaf49", "#49eeaf", "#af49ee"],
+            vec!["#336699", "#993399", "#996633", "#339933"],
+            vec!["#e0d1e0", "#ffffff", "#825782"],
+        ];
+        for ((primary, scheme), expected) in cases.into_iter().zip(expected) {
+            let scheme = ColorScheme::new(primary, scheme);
+            let actual: Vec<String> = scheme.entries().into_iter().map(|(_, v)| v).collect();
+            assert_eq!(actual, expected);
+        }
+    }
+    #[test]
+    fn test_order_luminance() {
+        let primary = Hsl::new(60.0, 100.0, 50.0, None);
+        let scheme = ColorScheme::from_schemes(primary, [Scheme::Column, Scheme::Tetrad])
+            .ordered_by(Order::Luminance);
+        let entries = scheme.entries();
+        assert_eq!(entries.len(), 6);
+        let luminances: Vec<f64> = entries
+            .iter()
+            .map(|(name, _)| crate::contrast::relative_luminance(scheme.get(name).unwrap()))
+            .collect();
+        assert!(luminances.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_ne!(entries[0].0, "--primary");
+    }
+    #[test]
+    fn test_dedup_hues() {
+        let mut colors = vec![
+            _new_hsl(120.0),
+            _new_hsl(122.0),
+            _new_hsl(240.0),
+            Hsl::new(121.0, 100.0, 20.0, None),
+        ];
+        dedup_hues(&mut colors, 5.0);
+        assert_eq!(
+            colors,
+            vec![
+                _new_hsl(120.0),
+                _new_hsl(240.0),
+                Hsl::new(121.0, 100.0, 20.0, None)
+            ]
+        );
+        let mut wrapping = vec![_new_hsl(359.0), _new_hsl(1.0)];
+        dedup_hues(&mut wrapping, 5.0);
+        assert_eq!(wrapping, vec![_new_hsl(359.0)]);
+    }
+    #[test]
+    fn test_is_custom_property_name() {
+        assert!(is_custom_property_name("--primary"));
+        assert!(is_custom_property_name("--rot-22_5"));
+        assert!(!is_custom_property_name("--"));
+        assert!(!is_custom_property_name("primary"));
+        assert!(!is_custom_property_name("1primary"));
+        assert!(!is_custom_property_name("--my color"));
+        assert!(!is_custom_property_name("--rot-22.5"));
+    }
+    #[test]
+    fn test_validate() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        assert_eq!(scheme.validate(), Ok(()));
+        let custom = ColorScheme {
+            colors: vec![(String::from("--my color"), _new_hsl(30.0))],
+            ..scheme
+        };
+        assert_eq!(
+            custom.validate(),
+            Err(String::from(
+                "invalid CSS custom property name `--my color`"
+            ))
+        );
+    }
+    #[test]
+    fn test_neutrals() {
+        let neutrals = ColorScheme::neutrals(&_new_hsl(90.0));
+        let expected: Vec<ColorVar> = vec![
+            (
+                String::from("--neutral-light"),
+                Hsl::new(90.0, 10.0, 95.0, Some(1.0)),
+            ),
+            (
+                String::from("--neutral"),
+                Hsl::new(90.0, 10.0, 50.0, Some(1.0)),
+            ),
+            (
+                String::from("--neutral-dark"),
+                Hsl::new(90.0, 10.0, 10.0, Some(1.0)),
+            ),
+        ];
+        assert_eq!(_hex(&neutrals), _hex(&expected));
+    }
+    #[test]
+    fn test_dark() {
+        let primary = Hsl::new(90.0, 50.0, 30.0, None);
+        let scheme = ColorScheme::new(primary, Scheme::Text);
+        let dark = scheme.dark();
+        assert_eq!(dark.get("--primary").unwrap().lightness(), 70.0);
+        assert_eq!(
+            dark.get("--text-primary").unwrap().lightness(),
+            100.0 - scheme.get("--text-primary").unwrap().lightness()
+        );
+    }
+    #[test]
+    fn test_dark_mode() {
+        let primary = Hsl::new(0.0, 100.0, 25.0, None);
+        let css = ColorScheme::new(primary, Scheme::Complementary)
+            .with_dark_mode()
+            .as_css(None);
+        assert!(css.ends_with(
+            "\n@media (prefers-color-scheme: dark) {\n\t:root {\n\t\t--primary: #ff8080;\n\t\t--complementary: #80ffff;\n\t}\n}"
+        ));
+    }
+    #[test]
+    fn test_as_light_dark() {
+        let primary = Hsl::new(0.0, 100.0, 25.0, None);
+        let expected = String::from(
+            ":root {\n\tcolor-scheme: light dark;\n\t--primary: light-dark(#800000, #ff8080);\n\t--complementary: light-dark(#008080, #80ffff);\n}",
+        );
+        let actual = ColorScheme::new(primary, Scheme::Complementary).as_light_dark(None);
+        assert_eq!(actual, expected);
+    }
+    #[test]
+    fn test_precision_loss() {
+        let precise = Hsl::new(123.456, 50.0, 40.0, None);
+        assert_eq!(
+            precision_loss(&precise, Format::Hex),
+            Some(String::from(
+                "hsl(123.456, 50%, 40%) can't be written exactly as #339939"
+            ))
+        );
+        assert!(precision_loss(&precise, Format::Hsl).is_some());
+        assert_eq!(precision_loss(&_new_hsl(0.0), Format::Hex), None);
+        assert_eq!(precision_loss(&_new_hsl(120.0), Format::Hsl), None);
+    }
+    #[test]
+    fn test_between() {
+        let from = _new_hsl(350.0);
+        let to = Hsl::new(30.0, 50.0, 90.0, Some(1.0));
+        let ramp = ColorScheme::between(from.clone(), to.clone(), 5);
+        assert_eq!(ramp.get("--step-0"), Some(&from));
+        assert_eq!(ramp.get("--step-4"), Some(&to));
+        assert_eq!(ramp.get("--step-5"), None);
+        let middle = ramp.get("--step-2").unwrap();
+        assert!((middle.hue() - 10.0).abs() < 1e-9);
+        assert!(!ramp.as_css(None).contains("--primary"));
+        let ramp = ColorScheme::between(_new_hsl(0.0), _new_hsl(45.0), 0);
+        assert_eq!(ramp.get("--step-1"), Some(&_new_hsl(45.0)));
+        assert_eq!(ramp.harmony_score(), 0.0);
+        let washed_out = Hsl::new(200.0, 50.0, 99.0, None);
+        let ramp = ColorScheme::between(washed_out, _new_hsl(200.0), 2);
+        assert_eq!(ramp.warnings().len(), 1);
+    }
+    #[test]
+    fn test_clamp_color() {
+        let mut out_of_range = _new_hsl(120.0);
+        out_of_range.set_lightness(f64::NAN);
+        let clamped = clamp_color(out_of_range);
+        assert_eq!(clamped.lightness(), 0.0);
+        assert_eq!(clamped.saturation(), 100.0);
+        let mut nan_saturation = _new_hsl(f64::NAN);
+        nan_saturation.set_saturation(f64::NAN);
+        let scheme = ColorScheme::new(nan_saturation, Scheme::Complementary);
+        assert_eq!(
+            scheme.get("--primary"),
+            Some(&Hsl::new(0.0, 0.0, 50.0, None))
+        );
+        assert_eq!(scheme.get("--complementary").map(|c| c.hue()), Some(180.0));
+        assert!(!scheme.as_css(None).contains("NaN"));
+    }
+    #[test]
+    fn test_clamp_alpha() {
+        let with_alpha = |a| Hsl::new(0.0, 100.0, 50.0, a);
+        assert_eq!(clamp_alpha(with_alpha(Some(1.5))).alpha(), 1.0);
+        assert_eq!(clamp_alpha(with_alpha(Some(-0.5))).alpha(), 0.0);
+        assert_eq!(clamp_alpha(with_alpha(Some(f64::NAN))).alpha(), 1.0);
+        assert_eq!(clamp_alpha(with_alpha(Some(0.5))).alpha(), 0.5);
+        assert_eq!(clamp_alpha(with_alpha(Some(1.0))), with_alpha(None));
+    }
+    #[test]
+    fn test_alpha_output() {
+        let primary = |a| {
+            ColorScheme::new(Hsl::new(0.0, 100.0, 50.0, a), Scheme::Complementary)
+                .get("--primary")
+                .map(hsl_to_css)
+        };
+        assert_eq!(primary(Some(0.0)), Some(String::from("#ff000000")));
+        assert_eq!(primary(Some(0.5)), Some(String::from("#ff000080")));
+        assert_eq!(primary(Some(1.0)), Some(String::from("#ff0000")));
+        assert_eq!(primary(None), Some(String::from("#ff0000")));
+        assert_eq!(primary(Some(2.0)), Some(String::from("#ff0000")));
+        assert_eq!(primary(Some(-1.0)), Some(String::from("#ff000000")));
+        let translucent = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_override(
+                "complementary",
+                Override::Color(Hsl::new(0.0, 100.0, 50.0, Some(-1.0))),
+            )
+            .as_css(None);
+        assert!(translucent.contains("\n\t--complementary: #ff000000;"));
+    }
+    #[test]
+    fn test_explain() {
+        let explanation = ColorScheme::new(_new_hsl(0.0), Scheme::Triad).explain();
+        assert_eq!(
+            explanation,
+            vec![
+                String::from("--clockwise: hue 0 +120 = 120 (#00ff00)"),
+                String::from("--counterclockwise: hue 0 -120 = 240 (#0000ff)"),
+            ]
+        );
+        let column = ColorScheme::new(_new_hsl(0.0), Scheme::Column).explain();
+        assert!(column[0].contains("lightness 50% -> 75%"));
+    }
+    #[test]
+    fn test_contrast_failures() {
+        let light = Hsl::new(0.0, 0.0, 55.0, None);
+        let scheme = ColorScheme::new(light, Scheme::Complementary).with_text_pairs();
+        assert!(scheme.contrast_failures(ContrastStandard::Aa).is_empty());
+        assert_eq!(scheme.contrast_failures(ContrastStandard::Apca).len(), 2);
+        let themed = ColorScheme::from_schemes(_new_hsl(200.0), [Scheme::Text, Scheme::Background]);
+        assert!(themed.contrast_failures(ContrastStandard::Aa).is_empty());
+    }
+    #[test]
+    fn test_excluding() {
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Tetrad)
+            .excluding("lower-left")
+            .as_css(None);
+        assert!(css.contains("\n\t--primary: "));
+        assert!(css.contains("\n\t--upper-right: "));
+        assert!(css.contains("\n\t--lower-right: "));
+        assert!(!css.contains("--lower-left"));
+        let prefixed = ColorScheme::new(_new_hsl(0.0), Scheme::Tetrad)
+            .excluding("--lower-left")
+            .as_css(None);
+        assert_eq!(css, prefixed);
+    }
+    #[test]
+    fn test_as_tailwind_theme() {
+        let theme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).as_tailwind_theme();
+        assert_eq!(
+            theme,
+            "@theme {\n\t--color-primary: #ff0000;\n\t--color-complementary: #00ffff;\n}"
+        );
+    }
+    #[test]
+    fn test_direction() {
+        let names = |scheme, direction| {
+            ColorScheme::from_schemes_in(_new_hsl(0.0), [scheme], direction)
+                .colors
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            names(Scheme::Triad, Direction::Clockwise),
+            vec![String::from("--clockwise")]
+        );
+        assert_eq!(
+            names(Scheme::Triad, Direction::Counterclockwise),
+            vec![String::from("--counterclockwise")]
+        );
+        assert_eq!(names(Scheme::Triad, Direction::Both).len(), 2);
+        assert_eq!(
+            names(Scheme::Tetrad, Direction::Counterclockwise),
+            vec![String::from("--lower-right"), String::from("--lower-left")]
+        );
+        assert_eq!(
+            names(Scheme::from_degrees([30.0, -30.0]), Direction::Clockwise),
+            vec![String::from("--rot-30")]
+        );
+        assert_eq!(
+            names(Scheme::Complementary, Direction::Counterclockwise),
+            vec![String::from("--complementary")]
+        );
+    }
+    #[test]
+    fn test_as_css_closing_brace() {
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Triad).as_css(Some(".theme"));
+        assert!(css.ends_with("\n}"));
+        assert!(!css.contains("};"));
+    }
+    #[test]
+    fn test_monochromatic() {
+        let mono = ColorScheme::monochromatic(&_new_hsl(200.0), 5);
+        let names: Vec<&str> = mono.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["--mono-1", "--mono-2", "--mono-3", "--mono-4", "--mono-5"]
+        );
+        let lightnesses: Vec<f64> = mono.iter().map(|(_, c)| c.lightness()).collect();
+        assert_eq!(lightnesses, vec![10.0, 30.0, 50.0, 70.0, 90.0]);
+        assert!(lightnesses.windows(2).all(|w| w[0] < w[1]));
+        assert!(
+            mono.iter()
+                .all(|(_, c)| c.hue() == 200.0 && c.saturation() == 100.0)
+        );
+        assert_eq!(
+            ColorScheme::monochromatic(&_new_hsl(0.0), 1)[0]
+                .1
+                .lightness(),
+            50.0
+        );
+        assert!(ColorScheme::monochromatic(&_new_hsl(0.0), 0).is_empty());
+    }
+    #[test]
+    fn test_format_red() {
+        let red = _new_hsl(0.0);
+        assert_eq!(format_color(&red, Format::Hex), "#ff0000");
+        assert_eq!(format_color(&red, Format::Rgb), "rgb(255, 0, 0)");
+        assert_eq!(format_color(&red, Format::Hsl), "hsl(0, 100%, 50%)");
+        assert_eq!(
+            format_color(&red, Format::Oklch),
+            "oklch(62.8% 0.258 29.23)"
+        );
+        let translucent = Hsl::new(0.0, 100.0, 50.0, Some(0.5));
+        assert_eq!(
+            format_color(&translucent, Format::Rgb),
+            "rgba(255, 0, 0, 0.5)"
+        );
+        let css = ColorScheme::new(red, Scheme::Complementary)
+            .with_format(Format::Rgb)
+            .as_css(None);
+        assert_eq!(
+            css,
+            ":root {\n\t--primary: rgb(255, 0, 0);\n\t--complementary: rgb(0, 255, 255);\n}"
+        );
+    }
+    #[test]
+    fn test_variables() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        let variables: Vec<(&str, &Hsl)> = scheme.variables().collect();
+        assert_eq!(
+            variables,
+            vec![
+                ("--primary", &_new_hsl(0.0)),
+                ("--clockwise", &_new_hsl(120.0)),
+                ("--counterclockwise", &_new_hsl(240.0)),
+            ]
+        );
+    }
+    #[test]
+    fn test_as_json() {
+        let json = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).as_json();
+        assert_eq!(
+            json,
+            r##"{"--primary":"#ff0000","--complementary":"#00ffff"}"##
+        );
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+    #[test]
+    fn test_as_scss() {
+        let scss = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).as_scss();
+        assert_eq!(scss, "$primary: #ff0000;\n$complementary: #00ffff;");
+        let tetrad = ColorScheme::new(_new_hsl(0.0), Scheme::Tetrad).as_scss();
+        assert!(tetrad.contains("\n$lower-right: "));
+    }
+    #[test]
+    fn test_with_prefix() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary);
+        assert_eq!(
+            scheme.clone().with_prefix("brand").as_css(None),
+            ":root {\n\t--brand-primary: #ff0000;\n\t--brand-complementary: #00ffff;\n}"
+        );
+        assert_eq!(
+            scheme.clone().with_prefix("--brand-").as_css(None),
+            scheme.clone().with_prefix("brand").as_css(None)
+        );
+        assert_eq!(
+            scheme.clone().with_prefix("").as_css(None),
+            scheme.as_css(None)
+        );
+        let light_dark = scheme.clone().with_prefix("brand").as_light_dark(None);
+        assert!(light_dark.contains("\n\t--brand-complementary: light-dark("));
+        assert_eq!(
+            scheme.with_prefix("brand").as_scss(),
+            "$brand-primary: #ff0000;\n$brand-complementary: #00ffff;"
+        );
+    }
+    #[test]
+    fn test_and_schemes() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .and_schemes([Scheme::Triad, Scheme::Tetrad]);
+        let names: Vec<&str> = scheme.variables().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "--primary",
+                "--complementary",
+                "--clockwise",
+                "--counterclockwise",
+                "--upper-right",
+                "--lower-right",
+                "--lower-left"
+            ]
+        );
+    }
+    #[test]
+    fn test_duplicate_schemes() {
+        let css = ColorScheme::from_schemes(
+            _new_hsl(0.0),
+            [Scheme::Complementary, Scheme::Complementary],
+        )
+        .and(Scheme::Complementary)
+        .as_css(None);
+        assert_eq!(css.matches("--complementary:").count(), 1);
+        let rotations = ColorScheme::new(_new_hsl(0.0), Scheme::from_degrees([30.0, 390.0]));
+        assert_eq!(rotations.variables().count(), 2);
+    }
+    #[test]
+    fn test_scheme_from_str() {
+        assert_eq!("triad".parse(), Ok(Scheme::Triad));
+        assert_eq!(
+            "split-complementary".parse(),
+            Ok(Scheme::SplitComplementary)
+        );
+        assert_eq!(
+            "Split_Complementary".parse(),
+            Ok(Scheme::SplitComplementary)
+        );
+        assert_eq!(
+            "hexad".parse::<Scheme>(),
+            Err(String::from("unknown scheme `hexad`"))
+        );
+        for scheme in NAMED_SCHEMES {
+            assert_eq!(scheme.name().unwrap().parse(), Ok(scheme));
+        }
+        assert_eq!(Scheme::Polyad(5).name(), None);
+    }
+    #[test]
+    fn test_pastel() {
+        let pastel = ColorScheme::pastel(&_new_hsl(200.0));
+        assert_eq!(pastel[0].0, "--pastel-primary");
+        assert_eq!(pastel[0].1.hue(), 200.0);
+        assert_eq!(pastel[0].1.saturation(), 40.0);
+        assert_eq!(pastel[0].1.lightness(), 85.0);
+    }
+    #[test]
+    fn test_meets_wcag_aa() {
+        let themed = ColorScheme::from_schemes(_new_hsl(200.0), [Scheme::Text, Scheme::Background]);
+        assert!(themed.meets_wcag_aa());
+        let text_only = ColorScheme::new(_new_hsl(200.0), Scheme::Text);
+        assert!(!text_only.meets_wcag_aa());
+    }
+    #[test]
+    fn test_as_css_with_dark() {
+        let light = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary);
+        let dark = ColorScheme::new(Hsl::new(0.0, 100.0, 20.0, None), Scheme::Complementary);
+        let css = light.as_css_with_dark(&dark, None);
+        let (light_block, dark_block) = css
+            .split_once("\n@media (prefers-color-scheme: dark) {")
+            .unwrap();
+        assert!(light_block.contains("\n\t--primary: #ff0000;"));
+        assert!(dark_block.contains("\n\t\t--primary: #660000;"));
+        assert_eq!(
+            light.clone().with_dark_mode().as_css(None),
+            light.as_css_with_dark(&light.dark(), None)
+        );
+    }
+    #[test]
+    fn test_as_html_preview() {
+        let html = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).as_html_preview();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>"));
+        assert!(html.contains(
+            "<div style=\"background: #ff0000; color: #000000; padding: 2em;\">--primary #ff0000</div>"
+        ));
+        assert!(html.contains(">--complementary #00ffff</div>"));
+        assert_eq!(html_escape("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+    #[test]
+    fn test_polyad() {
+        let polyad = ColorScheme::polyad(&_new_hsl(90.0), 3);
+        let triad = ColorScheme::triad(&_new_hsl(90.0));
+        assert_eq!(polyad.len(), 2);
+        assert_eq!(polyad[0].1, triad[0].1);
+        assert_eq!(polyad[1].1, triad[1].1);
+        assert_eq!(polyad[0].0, "--polyad-1");
+        let hexad: Vec<f64> = ColorScheme::polyad(&_new_hsl(0.0), 6)
+            .iter()
+            .map(|(_, c)| c.hue())
+            .collect();
+        assert_eq!(hexad, vec![60.0, 120.0, 180.0, 240.0, 300.0]);
+        assert!(ColorScheme::polyad(&_new_hsl(0.0), 1).is_empty());
+        assert!(ColorScheme::polyad(&_new_hsl(0.0), 0).is_empty());
+    }
+    #[test]
+    fn test_ramp() {
+        let ramp = ColorScheme::ramp(&_new_hsl(200.0));
+        let names: Vec<&str> = ramp.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["--tint-1", "--tint-2", "--shade-1", "--shade-2"]
+        );
+        let lightnesses: Vec<f64> = ramp.iter().map(|(_, c)| c.lightness()).collect();
+        assert_eq!(lightnesses, vec![65.0, 80.0, 35.0, 20.0]);
+        let light = ColorScheme::ramp(&Hsl::new(200.0, 100.0, 90.0, None));
+        assert_eq!(light[1].1.lightness(), 100.0);
+    }
+    #[test]
+    fn test_grayscale() {
+        let primary = Hsl::new(200.0, 70.0, 40.0, None);
+        let grayscale = ColorScheme::grayscale(&primary);
+        assert_eq!(grayscale[0].0, "--grayscale");
+        assert_eq!(grayscale[0].1.saturation(), 0.0);
+        assert_eq!(grayscale[0].1.hue(), 200.0);
+        assert_eq!(grayscale[0].1.lightness(), 40.0);
+    }
+    #[test]
+    fn test_from_rgb() {
+        let red = colorsys::Rgb::new(255.0, 0.0, 0.0, None);
+        assert_eq!(
+            ColorScheme::from_rgb(red.clone(), Scheme::Triad),
+            ColorScheme::new(_new_hsl(0.0), Scheme::Triad)
+        );
+        assert_eq!(
+            ColorScheme::from_rgb_schemes(red, [Scheme::Triad, Scheme::Text]),
+            ColorScheme::from_schemes(_new_hsl(0.0), [Scheme::Triad, Scheme::Text])
+        );
+    }
+    #[test]
+    fn test_display() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad).with_dark_mode();
+        assert_eq!(format!("{}", scheme), scheme.as_css(None));
+    }
+    #[test]
+    fn test_with_primary_name() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_primary_name("brand")
+            .with_text_pairs();
+        let css = scheme.as_css(None);
+        assert!(css.contains("\t--brand: #ff0000;"));
+        assert!(css.contains("\t--brand-text: "));
+        assert!(!css.contains("--primary"));
+        assert_eq!(scheme.get("--brand"), Some(&_new_hsl(0.0)));
+    }
+    #[test]
+    fn test_default_primary_name() {
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).as_css(None);
+        assert!(css.contains("\t--primary: #ff0000;"));
+    }
+    #[test]
+    fn test_from_css_round_trip() {
+        let scheme =
+            ColorScheme::from_schemes(_new_hsl(0.0), [Scheme::Triad, Scheme::Complementary]);
+        assert_eq!(
+            ColorScheme::from_css(&scheme.as_css(None)),
+            Ok(scheme.clone())
+        );
+        let css = format!("{}\n", scheme.as_css(Some(".theme")));
+        assert_eq!(ColorScheme::from_css(&css), Ok(scheme.clone()));
+        let brand = scheme.clone().with_primary_name("brand");
+        assert_eq!(
+            ColorScheme::from_css(&brand.as_css(None)),
+            Err(String::from("no `--primary` variable found"))
+        );
+        assert_eq!(
+            ColorScheme::from_css_with_primary(&brand.as_css(None), "brand"),
+            Ok(brand)
+        );
+        let prefixed = ColorScheme::from_css_with_primary(
+            &scheme.clone().with_prefix("brand").as_css(None),
+            "--brand-primary",
+        )
+        .unwrap();
+        assert_eq!(prefixed.get("--brand-primary"), Some(&_new_hsl(0.0)));
+        assert_eq!(prefixed.get("--brand-clockwise"), Some(&_new_hsl(120.0)));
+    }
+    #[test]
+    fn test_from_css_values() {
+        let scheme =
+            ColorScheme::from_css(":root{--primary:#f00;\n  --accent :  rgb(0, 0, 255)  }")
+                .unwrap();
+        assert_eq!(scheme.get("--primary"), Some(&_new_hsl(0.0)));
+        assert_eq!(scheme.get("--accent"), Some(&_new_hsl(240.0)));
+    }
+    #[test]
+    fn test_from_css_errors() {
+        assert!(ColorScheme::from_css("--primary: red;").is_err());
+        assert!(ColorScheme::from_css(":root { --accent: red; }").is_err());
+        assert!(ColorScheme::from_css(":root { --primary: nope; }").is_err());
+        assert!(ColorScheme::from_css(":root { color: red; }").is_err());
+    }
+    #[test]
+    fn test_rotated() {
+        let scheme = ColorScheme::rotated(_new_hsl(10.0), 450.0);
+        assert_eq!(scheme, ColorScheme::rotated(_new_hsl(10.0), 90.0));
+        assert_eq!(scheme.get("--rotated-90"), Some(&_new_hsl(100.0)));
+        let scheme = ColorScheme::rotated(_new_hsl(10.0), -30.0);
+        assert_eq!(scheme.get("--rotated-330"), Some(&_new_hsl(340.0)));
+        for degrees in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let scheme = ColorScheme::rotated(_new_hsl(10.0), degrees);
+            assert_eq!(scheme.variables().count(), 1);
+        }
+    }
+    #[test]
+    fn test_rotations_dont_drift() {
+        let primary = _new_hsl(10.0);
+        let rotated = (0..3600).fold(primary.clone(), |color, _| rotate(&color, 0.1));
+        assert_eq!(rotated, primary);
+        let primary = Hsl::from(colorsys::Rgb::new(51.0, 153.0, 57.0, None));
+        let rotated = [120.0, 45.5, 74.5, 120.0]
+            .iter()
+            .fold(primary.clone(), |color, by| rotate(&color, *by));
+        assert_eq!(hsl_to_css(&rotated), hsl_to_css(&primary));
+    }
+    #[test]
+    fn test_as_css_with_fallbacks() {
+        let scheme =
+            ColorScheme::new(_new_hsl(0.0), Scheme::Complementary).with_format(Format::Hsl);
+        let css = scheme.as_css_with_fallbacks(Some(".theme"));
+        assert!(
+            css.starts_with(".theme {\n\t--primary: #ff0000;\n\t--complementary: #00ffff;\n}\n")
+        );
+        assert!(css.ends_with(&scheme.as_css(Some(".theme"))));
+        assert!(!css.contains("var("));
+        let css = scheme
+            .with_override("complementary", Override::Keyword(String::from("inherit")))
+            .with_dark_mode()
+            .as_css_with_fallbacks(None);
+        let (hex, rest) = css.split_once("\n}\n").unwrap();
+        assert_eq!(hex, ":root {\n\t--primary: #ff0000;");
+        assert!(rest.contains("--complementary: inherit;"));
+        let media = css.find("@media (prefers-color-scheme: dark)").unwrap();
+        assert!(css.rfind(":root {").unwrap() > media);
+        assert!(css.ends_with("\n\t}\n}"));
+    }
+    #[test]
+    fn test_as_tailwind() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary);
+        assert_eq!(
+            scheme.as_tailwind(),
+            r##"{ "primary": "#ff0000", "complementary": "#00ffff" }"##
+        );
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad).with_prefix("brand");
+        assert!(
+            scheme
+                .as_tailwind()
+                .contains(r##""brand-clockwise": "#00ff00""##)
+        );
+    }
+    #[test]
+    fn test_from_no_schemes() {
+        let scheme = ColorScheme::from_schemes(_new_hsl(0.0), []);
+        assert_eq!(scheme.as_css(None), ":root {\n\t--primary: #ff0000;\n}");
+    }
+    #[test]
+    fn test_into_iter() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        let mut names = Vec::new();
+        for (name, _) in &scheme {
+            names.push(name);
+        }
+        assert_eq!(
+            names,
+            vec!["--primary", "--clockwise", "--counterclockwise"]
+        );
+    }
+    #[test]
+    fn test_css_selector() {
+        let scheme = ColorScheme::from_schemes(_new_hsl(0.0), []);
+        let root = scheme.as_css(None);
+        assert_eq!(scheme.as_css(Some("")), root);
+        assert_eq!(scheme.as_css(Some(" \t ")), root);
+        assert_eq!(scheme.as_css(Some("a { color: red; }")), root);
+        assert_eq!(scheme.as_css(Some("}")), root);
+        assert!(scheme.as_css(Some("  .theme ")).starts_with(".theme {"));
+    }
+    #[test]
+    fn test_as_css_minified() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary);
+        assert_eq!(
+            scheme.as_css_minified(None),
+            ":root{--primary:#ff0000;--complementary:#00ffff}"
+        );
+        let dark = ColorScheme::new(Hsl::new(0.0, 100.0, 25.0, None), Scheme::Complementary)
+            .with_dark_mode()
+            .as_css_minified(Some(".theme"));
+        assert_eq!(
+            dark,
+            ".theme{--primary:#800000;--complementary:#008080}@media (prefers-color-scheme:dark){.theme{--primary:#ff8080;--complementary:#80ffff}}"
+        );
+        let functional =
+            ColorScheme::new(Hsl::new(0.0, 100.0, 50.0, Some(0.5)), Scheme::Complementary)
+                .with_format(Format::Rgb)
+                .as_css_minified(None);
+        assert_eq!(
+            functional,
+            ":root{--primary:rgba(255,0,0,0.5);--complementary:rgba(0,255,255,0.5)}"
+        );
+        let oklch = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_format(Format::Oklch)
+            .as_css_minified(None);
+        assert!(oklch.starts_with(":root{--primary:oklch(") && oklch.contains(' '));
+        #[cfg(feature = "named-colors")]
+        assert_eq!(
+            ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+                .with_color_names()
+                .as_css_minified(None),
+            ":root{--primary:#ff0000;--complementary:#00ffff}"
+        );
+    }
+    #[test]
+    fn test_perceptual_monochromatic() {
+        let primary = Hsl::new(60.0, 100.0, 50.0, Some(0.5));
+        let lightnesses: Vec<f64> = ColorScheme::perceptual_monochromatic(&primary, 5)
+            .iter()
+            .map(|(_, color)| Oklch::from(color).l)
+            .collect();
+        for (l, expected) in lightnesses.iter().zip([0.1, 0.3, 0.5, 0.7, 0.9]) {
+            assert!((l - expected).abs() < 1e-3);
+        }
+        // hsl steps are uneven in oklab: yellow's 30% to 50% is a far bigger jump than 50% to 70%
+        let hsl: Vec<f64> = ColorScheme::monochromatic(&primary, 5)
+            .iter()
+            .map(|(_, color)| Oklch::from(color).l)
+            .collect();
+        assert!((hsl[2] - hsl[1]) - (hsl[3] - hsl[2]) > 0.2);
+        let mono = ColorScheme::perceptual_monochromatic(&primary, 2);
+        assert_eq!(mono[0].1.alpha(), 0.5);
+    }
+    #[test]
+    fn test_perceptual_column() {
+        let primary = _new_hsl(240.0);
+        let lightness = Oklch::from(&primary).l;
+        let column = ColorScheme::perceptual_column(&primary);
+        assert_eq!(column[0].0, "--lighter");
+        assert!((Oklch::from(&column[0].1).l - (lightness + 0.2)).abs() < 1e-3);
+        assert!((Oklch::from(&column[1].1).l - (lightness - 0.2)).abs() < 1e-3);
+    }
+    #[test]
+    fn test_alpha() {
+        let primary = _new_hsl(200.0);
+        let alpha = ColorScheme::alpha(&primary);
+        let names: Vec<&str> = alpha.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["--primary-90", "--primary-50", "--primary-10"]);
+        let alphas: Vec<f64> = alpha.iter().map(|(_, color)| color.alpha()).collect();
+        assert_eq!(alphas, vec![0.9, 0.5, 0.1]);
+        for (_, color) in &alpha {
+            assert_eq!(
+                (color.hue(), color.saturation(), color.lightness()),
+                (primary.hue(), primary.saturation(), primary.lightness())
+            );
+        }
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Alpha).as_css(None);
+        assert!(css.contains("\n\t--primary-50: #ff000080;"));
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Alpha)
+            .with_format(Format::Rgb)
+            .as_css(None);
+        assert!(css.contains("\n\t--primary-10: rgba(255, 0, 0, 0.1);"));
+    }
+    #[test]
+    fn test_eq_rendered() {
+        let scheme = ColorScheme::new(_new_hsl(0.0), Scheme::Triad);
+        let drifted = ColorScheme::new(_new_hsl(1e-9), Scheme::Triad).with_format(Format::Hsl);
+        assert_ne!(scheme, drifted);
+        assert!(scheme.eq_rendered(&drifted));
+        assert!(!scheme.eq_rendered(&ColorScheme::new(_new_hsl(0.0), Scheme::Tetrad)));
+        assert!(!scheme.eq_rendered(&ColorScheme::new(_new_hsl(10.0), Scheme::Triad)));
+    }
+    #[test]
+    #[cfg(feature = "named-colors")]
+    fn test_with_color_names() {
+        let css = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary)
+            .with_override("complementary", Override::Keyword(String::from("inherit")))
+            .and(Scheme::Triad)
+            .with_color_names()
+            .as_css(None);
+        assert_eq!(
+            css,
+            ":root {\n\t--primary: #ff0000; /* red */\n\t--complementary: inherit;\n\t--clockwise: #00ff00; /* lime */\n\t--counterclockwise: #0000ff; /* blue */\n}"
+        );
+    }
+    #[test]
+    fn test_text_background_contrast() {
+        for hue in (0..360).step_by(15) {
+            for lightness in [5.0, 50.0, 95.0] {
+                let primary = Hsl::new(f64::from(hue), 100.0, lightness, None);
+                let (text, background) = ColorScheme::text_and_background(&primary);
+                assert!(contrast_ratio(&text, &background) >= TEXT_CONTRAST_TARGET);
+            }
+        }
+        let dark = Hsl::new(240.0, 60.0, 8.0, None);
+        let scheme = ColorScheme::from_schemes(dark, [Scheme::Text, Scheme::Background]);
+        assert!(scheme.meets_wcag_aa());
+    }
+    #[test]
+    fn test_text_background_follow_primary() {
+        let dark = Hsl::new(0.0, 100.0, 6.0, None);
+        let light = Hsl::new(0.0, 100.0, 94.0, None);
+        let (dark_text, dark_background) = ColorScheme::text_and_background(&dark);
+        let (light_text, light_background) = ColorScheme::text_and_background(&light);
+        // a dark primary pushes its text darker and its background lighter
+        assert!(dark_text.lightness() < light_text.lightness());
+        assert!(dark_background.lightness() > light_background.lightness());
+        assert!(contrast_ratio(&dark_text, &dark_background) >= TEXT_CONTRAST_TARGET);
+        // a light primary starts out below the target, so the solver kicks in
+        assert!(contrast_ratio(&light_text, &light_background) >= TEXT_CONTRAST_TARGET);
+        assert!(light_text.lightness() < 5.0 + 30.0 * relative_luminance(&light));
+    }
+    #[test]
+    fn test_sorted() {
+        let scheme =
+            ColorScheme::from_schemes(_new_hsl(0.0), [Scheme::Triad, Scheme::Complementary]);
+        let names = |scheme: &ColorScheme| -> Vec<String> {
+            scheme.entries().into_iter().map(|(name, _)| name).collect()
+        };
+        assert_eq!(
+            names(&scheme),
+            vec![
+                "--primary",
+                "--clockwise",
+                "--counterclockwise",
+                "--complementary"
+            ]
+        );
+        assert_eq!(
+            names(&scheme.clone().sorted()),
+            vec![
+                "--primary",
+                "--clockwise",
+                "--complementary",
+                "--counterclockwise"
+            ]
+        );
+        assert_eq!(
+            names(&scheme.clone().without_primary().sorted()),
+            vec!["--clockwise", "--complementary", "--counterclockwise"]
+        );
+        assert_eq!(
+            names(&scheme.clone().excluding("primary").sorted()),
+            vec!["--clockwise", "--complementary", "--counterclockwise"]
+        );
+        let everything = ["primary", "clockwise", "counterclockwise", "complementary"]
+            .into_iter()
+            .fold(scheme, |scheme, name| scheme.excluding(name));
+        assert!(names(&everything.sorted()).is_empty());
+    }
+    #[test]
+    fn test_builder() {
+        let scheme = ColorSchemeBuilder::new()
+            .primary(_new_hsl(0.0))
+            .complementary()
+            .monochromatic(2)
+            .rotated(45.0)
+            .column_step(10.0)
+            .prefix("brand")
+            .build()
+            .unwrap();
+        let expected = ColorScheme::from_schemes(
+            _new_hsl(0.0),
+            [
+                Scheme::Complementary,
+                Scheme::Monochromatic(2),
+                Scheme::Rotated(45.0),
+                Scheme::SteppedColumn(10.0),
+            ],
+        )
+        .with_prefix("brand");
+        assert_eq!(scheme, expected);
+        let names: Vec<String> = scheme.entries().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "--brand-primary",
+                "--brand-complementary",
+                "--brand-mono-1",
+                "--brand-mono-2",
+                "--brand-rotated-45",
+                "--brand-lighter",
+                "--brand-darker",
+            ]
+        );
+        assert!(ColorSchemeBuilder::new().complementary().build().is_err());
+    }
+    #[test]
+    fn test_merge() {
+        let base = ColorScheme::new(_new_hsl(0.0), Scheme::Complementary);
+        let accent = ColorScheme::new(_new_hsl(90.0), Scheme::Triad).with_prefix("accent");
+        let merged = base.merge(accent);
+        let names: Vec<&str> = merged.variables().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "--primary",
+                "--complementary",
+                "--clockwise",
+                "--counterclockwise"
+            ]
+        );
+        assert_eq!(merged.get("--primary"), Some(&_new_hsl(0.0)));
+        assert_eq!(merged.get("--clockwise").map(|c| c.hue()), Some(210.0));
+        assert!(!merged.as_css(None).contains("--accent"));
+        let again = merged
+            .clone()
+            .merge(ColorScheme::new(_new_hsl(270.0), Scheme::Complementary));
+        assert_eq!(again, merged);
+    }
+    #[test]
+    fn test_canonical_order() {
+        let scheme = ColorScheme::from_schemes(
+            _new_hsl(0.0),
+            [
+                Scheme::Background,
+                Scheme::Triad,
+                Scheme::Column,
+                Scheme::Text,
+                Scheme::Complementary,
+            ],
+        )
+        .with_text_pairs()
+        .canonically_ordered();
+        let names: Vec<String> = scheme.entries().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "--primary",
+                "--primary-text",
+                "--lighter",
+                "--lighter-text",
+                "--darker",
+                "--darker-text",
+                "--clockwise",
+                "--clockwise-text",
+                "--counterclockwise",
+                "--counterclockwise-text",
+                "--complementary",
+                "--complementary-text",
+                "--background-primary",
+                "--background-primary-text",
+                "--text-primary",
+                "--text-primary-text",
+            ]
+        );
+        assert_eq!(canonical_group("--rot-22_5"), 1);
+        assert_eq!(canonical_group("--rotation"), 0);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let scheme = ColorScheme::new(Hsl::new(200.0, 60.0, 40.0, Some(0.5)), Scheme::Triad);
+        let json = serde_json::to_string(&scheme).unwrap();
+        assert_eq!(serde_json::from_str::<ColorScheme>(&json).unwrap(), scheme);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_scheme_names() {
+        assert_eq!(
+            serde_json::to_string(&Scheme::SplitComplementary).unwrap(),
+            "\"split-complementary\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Scheme>("\"triad\"").unwrap(),
+            Scheme::Triad
+        );
+        assert_eq!(serde_json::to_string(&Scheme::Alpha).unwrap(), "\"alpha\"");
+        assert!(serde_json::to_string(&Scheme::Monochromatic(3)).is_err());
+        assert!(serde_json::from_str::<Scheme>("\"monochromatic\"").is_err());
+    }
 }